@@ -0,0 +1,74 @@
+use crate::attempt::ParseAttemptError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+};
+
+#[derive(Debug)]
+pub enum AssistantError {
+    ParseError(ParseAttemptError),
+    InputLengthMismatch,
+    NoCandidates,
+    NothingToUndo,
+    BoardCountMismatch(usize),
+    GuessMismatch,
+    IoError(io::Error),
+}
+
+#[cfg(test)]
+impl PartialEq for AssistantError {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::ParseError(e) => matches!(other, Self::ParseError(oe) if e == oe),
+            Self::InputLengthMismatch => matches!(other, Self::InputLengthMismatch),
+            Self::NoCandidates => matches!(other, Self::NoCandidates),
+            Self::NothingToUndo => matches!(other, Self::NothingToUndo),
+            Self::BoardCountMismatch(count) => {
+                matches!(other, Self::BoardCountMismatch(other_count) if count == other_count)
+            }
+            Self::GuessMismatch => matches!(other, Self::GuessMismatch),
+            Self::IoError(io) => {
+                matches!(other, Self::IoError(other_io) if io.kind() == other_io.kind())
+            }
+        }
+    }
+}
+
+impl Error for AssistantError {}
+
+impl From<ParseAttemptError> for AssistantError {
+    #[inline]
+    fn from(value: ParseAttemptError) -> Self {
+        Self::ParseError(value)
+    }
+}
+
+impl From<io::Error> for AssistantError {
+    #[inline]
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl Display for AssistantError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError(parse_attempt_error) => {
+                write!(f, "Parse error: {parse_attempt_error}")
+            }
+            Self::InputLengthMismatch => write!(f, "Input string length not matched to word"),
+            Self::NoCandidates => write!(
+                f,
+                "No dictionary word is consistent with this feedback, \
+                check it for typos or the dictionary may differ from the game's one"
+            ),
+            Self::NothingToUndo => write!(f, "Nothing to undo"),
+            Self::BoardCountMismatch(count) => {
+                write!(f, "Expected feedback for {count} unsolved boards")
+            }
+            Self::GuessMismatch => write!(f, "Feedback rows are for different guesses"),
+            Self::IoError(err) => write!(f, "I/O Error: {err}"),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{assistant::AssistantError, solver::jotto, Dict, JottoAttempt};
+use itertools::Itertools;
+use std::io::{BufRead, Write};
+
+pub struct JottoAssistant<'a> {
+    dict: &'a Dict,
+    history: Vec<JottoAttempt>,
+    candidates: Dict,
+}
+
+impl<'a> JottoAssistant<'a> {
+    pub fn new(dict: &'a Dict) -> Self {
+        Self {
+            dict,
+            history: vec![],
+            candidates: dict.clone(),
+        }
+    }
+
+    #[inline]
+    pub fn history(&self) -> &[JottoAttempt] {
+        &self.history
+    }
+
+    #[inline]
+    pub fn candidates(&self) -> &Dict {
+        &self.candidates
+    }
+
+    pub fn observe(&mut self, attempt: JottoAttempt) -> Result<&Dict, AssistantError> {
+        if self.dict.words().first().map_or(false, |word| {
+            word.chars().count() != attempt.word.chars().count()
+        }) {
+            return Err(AssistantError::InputLengthMismatch);
+        }
+        let candidates = jotto::candidates(&self.candidates, [&attempt]);
+        if candidates.words().is_empty() {
+            return Err(AssistantError::NoCandidates);
+        }
+        self.candidates = candidates;
+        self.history.push(attempt);
+        Ok(&self.candidates)
+    }
+
+    pub fn undo(&mut self) -> Result<JottoAttempt, AssistantError> {
+        let attempt = self.history.pop().ok_or(AssistantError::NothingToUndo)?;
+        self.candidates = jotto::candidates(self.dict, &self.history);
+        Ok(attempt)
+    }
+
+    #[inline]
+    pub fn suggest(&self) -> Option<&'static str> {
+        jotto::suggest_word(&self.candidates)
+    }
+
+    pub fn main_loop(
+        &mut self,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<(), AssistantError> {
+        let mut lines = r.lines();
+        loop {
+            match self.suggest() {
+                Some(word) => writeln!(
+                    w,
+                    "Suggestion: {word} ({} candidates)",
+                    self.candidates.words().len()
+                )?,
+                None => writeln!(w, "No suggestion")?,
+            }
+            write!(w, "Enter guess with counts (/undo, /list): ")?;
+            w.flush()?;
+
+            let Some(line) = lines.next() else {
+                writeln!(w)?;
+                return Ok(());
+            };
+            match line?.to_lowercase().trim() {
+                "" => {}
+                "/undo" => match self.undo() {
+                    Ok(attempt) => writeln!(w, "Undone: {attempt}")?,
+                    Err(err) => writeln!(w, "{err}")?,
+                },
+                "/list" => writeln!(w, "{}", self.candidates.words().iter().join(" "))?,
+                line => match line
+                    .parse::<JottoAttempt>()
+                    .map_err(AssistantError::from)
+                    .and_then(|attempt| {
+                        let is_win_attempt = attempt.is_win_attempt();
+                        self.observe(attempt).map(|_| is_win_attempt)
+                    }) {
+                    Ok(true) => {
+                        writeln!(w, "Solved!")?;
+                        return Ok(());
+                    }
+                    Ok(false) => {}
+                    Err(err) => writeln!(w, "{err}")?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attempt::ParseAttemptError;
+    use std::io::Cursor;
+
+    fn dict() -> Dict {
+        Dict::from_iter(["казан", "казна", "сазан", "фазан"])
+    }
+
+    #[test]
+    fn observe() {
+        let dict = dict();
+        let mut assistant = JottoAssistant::new(&dict);
+        assert_eq!(
+            assistant
+                .observe("казна 2+2?".parse().unwrap())
+                .map(Dict::words),
+            Ok(&["сазан", "фазан"][..])
+        );
+        assert_eq!(
+            assistant.observe("каз 2+0?".parse().unwrap()).map(|_| ()),
+            Err(AssistantError::InputLengthMismatch)
+        );
+        assert_eq!(
+            assistant.observe("казан 5+0?".parse().unwrap()).map(|_| ()),
+            Err(AssistantError::NoCandidates)
+        );
+        assert_eq!(assistant.history().len(), 1);
+        assert_eq!(
+            assistant.undo().map(|attempt| attempt.to_string()),
+            Ok("казна 2+2?".to_string())
+        );
+        assert_eq!(assistant.candidates(), &dict);
+        assert_eq!(
+            assistant.undo().map(|_| ()),
+            Err(AssistantError::NothingToUndo)
+        );
+    }
+
+    #[test]
+    fn main_loop() {
+        let dict = dict();
+        let mut assistant = JottoAssistant::new(&dict);
+        let mut out = vec![];
+        let mut inp =
+            Cursor::new("казна 2+2?\n/list\n/undo\nказан 2\nказан 9+9?\nказан 4+0?\nсазан 5+0?\n");
+        assert_eq!(assistant.main_loop(&mut inp, &mut out), Ok(()));
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            format!(
+                "\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with counts (/undo, /list): \
+                Suggestion: сазан (2 candidates)\n\
+                Enter guess with counts (/undo, /list): сазан фазан\n\
+                Suggestion: сазан (2 candidates)\n\
+                Enter guess with counts (/undo, /list): Undone: казна 2+2?\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with counts (/undo, /list): Parse error: {}\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with counts (/undo, /list): Parse error: {}\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with counts (/undo, /list): \
+                Suggestion: сазан (2 candidates)\n\
+                Enter guess with counts (/undo, /list): Solved!\n\
+                ",
+                ParseAttemptError::JottoCountsUnexpected("2".to_string()),
+                ParseAttemptError::JottoCountsExceedLength("9+9?".to_string())
+            )
+        );
+    }
+}
@@ -0,0 +1,278 @@
+mod error;
+mod jotto;
+mod multi_board;
+
+pub use error::AssistantError;
+pub use jotto::JottoAssistant;
+pub use multi_board::MultiBoardAssistant;
+
+use crate::{
+    solver::{Solver, Strategy, Suggestion},
+    Attempt, Dict,
+};
+use itertools::Itertools;
+use std::io::{BufRead, Write};
+
+const RANK_TOP: usize = 10;
+
+pub struct Assistant<'a> {
+    dict: &'a Dict,
+    history: Vec<Attempt>,
+    solver: Box<dyn Solver + 'a>,
+}
+
+impl<'a> Assistant<'a> {
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_solver(dict, Strategy::Frequency.solver(dict))
+    }
+
+    pub fn with_solver(dict: &'a Dict, solver: Box<dyn Solver + 'a>) -> Self {
+        Self {
+            dict,
+            history: vec![],
+            solver,
+        }
+    }
+
+    #[inline]
+    pub fn history(&self) -> &[Attempt] {
+        &self.history
+    }
+
+    #[inline]
+    pub fn candidates(&self) -> &Dict {
+        self.solver.candidates()
+    }
+
+    pub fn observe(&mut self, attempt: Attempt) -> Result<&Dict, AssistantError> {
+        let Attempt(attempt_chars) = &attempt;
+        if self
+            .dict
+            .words()
+            .first()
+            .map_or(false, |word| word.chars().count() != attempt_chars.len())
+        {
+            return Err(AssistantError::InputLengthMismatch);
+        }
+        if !self.solver.is_consistent(&attempt) {
+            return Err(AssistantError::NoCandidates);
+        }
+        self.solver.observe(&attempt);
+        self.history.push(attempt);
+        Ok(self.solver.candidates())
+    }
+
+    pub fn undo(&mut self) -> Result<Attempt, AssistantError> {
+        let attempt = self.history.pop().ok_or(AssistantError::NothingToUndo)?;
+        self.solver.start();
+        for attempt in &self.history {
+            self.solver.observe(attempt);
+        }
+        Ok(attempt)
+    }
+
+    pub fn suggest(&self) -> Option<String> {
+        self.solver.suggest().map(str::to_string)
+    }
+
+    #[inline]
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        self.solver.suggestion()
+    }
+
+    pub fn rank(&self) -> Vec<(&'static str, f64)> {
+        self.solver.rank(self.dict)
+    }
+
+    pub fn main_loop(
+        &mut self,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<(), AssistantError> {
+        let mut lines = r.lines();
+        loop {
+            match self.suggestion() {
+                Some(Suggestion { word, proven }) => writeln!(
+                    w,
+                    "Suggestion: {word} ({} candidates{})",
+                    self.candidates().words().len(),
+                    if proven { ", proven optimal" } else { "" }
+                )?,
+                None => writeln!(w, "No suggestion")?,
+            }
+            write!(w, "Enter guess with feedback (/undo, /list, /rank): ")?;
+            w.flush()?;
+
+            let Some(line) = lines.next() else {
+                writeln!(w)?;
+                return Ok(());
+            };
+            match line?.to_lowercase().trim() {
+                "" => {}
+                "/undo" => match self.undo() {
+                    Ok(attempt) => writeln!(w, "Undone: {attempt}")?,
+                    Err(err) => writeln!(w, "{err}")?,
+                },
+                "/list" => writeln!(w, "{}", self.candidates().words().iter().join(" "))?,
+                "/rank" => {
+                    for (word, entropy) in self.rank().into_iter().take(RANK_TOP) {
+                        writeln!(w, "{word}: {entropy:.3}")?;
+                    }
+                }
+                line => match Attempt::parse_compact(line)
+                    .map_err(AssistantError::from)
+                    .and_then(|attempt| {
+                        let is_win_attempt = attempt.is_win_attempt();
+                        self.observe(attempt).map(|_| is_win_attempt)
+                    }) {
+                    Ok(true) => {
+                        writeln!(w, "Solved!")?;
+                        return Ok(());
+                    }
+                    Ok(false) => {}
+                    Err(err) => writeln!(w, "{err}")?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attempt::ParseAttemptError;
+    use std::io::Cursor;
+
+    #[test]
+    fn observe() {
+        let dict = Dict::default();
+        let mut assistant = Assistant::new(&dict);
+        let candidates = assistant
+            .observe(Attempt::parse_compact("к а+з+н?а?").unwrap())
+            .unwrap();
+        assert!(candidates.word_in_dict("сазан"));
+        assert!(!candidates.word_in_dict("казна"));
+        assert_eq!(assistant.history().len(), 1);
+    }
+
+    #[test]
+    fn observe_no_candidates() {
+        let dict = Dict::default();
+        let mut assistant = Assistant::new(&dict);
+        assistant
+            .observe(Attempt::parse_compact("с+а+з+а+н+").unwrap())
+            .unwrap();
+        assert_eq!(
+            assistant
+                .observe(Attempt::parse_compact("ф+а+з+а+н+").unwrap())
+                .map(|_| ()),
+            Err(AssistantError::NoCandidates)
+        );
+        assert_eq!(assistant.history().len(), 1);
+    }
+
+    #[test]
+    fn observe_length_mismatch() {
+        let dict = Dict::default();
+        let mut assistant = Assistant::new(&dict);
+        assert_eq!(
+            assistant
+                .observe(Attempt::parse_compact("с+а+з+").unwrap())
+                .map(|_| ()),
+            Err(AssistantError::InputLengthMismatch)
+        );
+    }
+
+    #[test]
+    fn undo() {
+        let dict = Dict::default();
+        let mut assistant = Assistant::new(&dict);
+        assert_eq!(
+            assistant.undo().map(|_| ()),
+            Err(AssistantError::NothingToUndo)
+        );
+        let attempt = Attempt::parse_compact("к а+з+н?а?").unwrap();
+        assistant.observe(attempt.clone()).unwrap();
+        assert_eq!(assistant.undo(), Ok(attempt));
+        assert_eq!(assistant.candidates(), &dict);
+    }
+
+    #[test]
+    fn suggest() {
+        let dict = Dict::default();
+        let mut assistant = Assistant::new(&dict);
+        assert_eq!(assistant.suggest(), Some("щетка".to_string()));
+        assistant
+            .observe(Attempt::parse_compact("к а+з+н?а?").unwrap())
+            .unwrap();
+        let suggestion = assistant.suggest().unwrap();
+        assert!(assistant.candidates().word_in_dict(&suggestion));
+    }
+
+    #[test]
+    fn with_solver() {
+        let dict = Dict::from_iter(["казан", "казна", "сазан", "фазан"]);
+        let mut assistant = Assistant::with_solver(&dict, Strategy::Minimax.solver(&dict));
+        assert_eq!(assistant.suggest(), Some("казан".to_string()));
+        assistant
+            .observe(Attempt::parse_compact("ка+з+н?а?").unwrap())
+            .unwrap();
+        assert_eq!(assistant.candidates().words(), ["сазан", "фазан"]);
+        assistant.undo().unwrap();
+        assert_eq!(assistant.candidates(), &dict);
+    }
+
+    #[test]
+    fn main_loop_proven() {
+        let dict = Dict::from_iter(["казан", "казна", "сазан", "фазан"]);
+        let mut assistant = Assistant::with_solver(&dict, Strategy::Anytime.solver(&dict));
+        let mut out = vec![];
+        let mut inp = Cursor::new("ка+з+н?а?\nс+а+з+а+н+\n");
+        assert_eq!(assistant.main_loop(&mut inp, &mut out), Ok(()));
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "\
+            Suggestion: казан (4 candidates, proven optimal)\n\
+            Enter guess with feedback (/undo, /list, /rank): \
+            Suggestion: сазан (2 candidates, proven optimal)\n\
+            Enter guess with feedback (/undo, /list, /rank): Solved!\n\
+            "
+        );
+    }
+
+    #[test]
+    fn main_loop() {
+        let dict = Dict::from_iter(["казан", "казна", "сазан", "фазан"]);
+        let mut assistant = Assistant::new(&dict);
+        let mut out = vec![];
+        let mut inp = Cursor::new("ка+з+н?а?\n/rank\n/undo\nк++\nказна\n/list\nс+а+з+а+н+\n");
+        assert_eq!(assistant.main_loop(&mut inp, &mut out), Ok(()));
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            format!(
+                "\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): \
+                Suggestion: сазан (2 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): \
+                сазан: 1.000\n\
+                фазан: 1.000\n\
+                казан: 0.000\n\
+                казна: 0.000\n\
+                Suggestion: сазан (2 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): Undone: к а+з+н?а?\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): Parse error: {}\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): {}\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): казан казна сазан фазан\n\
+                Suggestion: казан (4 candidates)\n\
+                Enter guess with feedback (/undo, /list, /rank): Solved!\n\
+                ",
+                ParseAttemptError::CharResultUnexpected('+'),
+                AssistantError::NoCandidates
+            )
+        );
+    }
+}
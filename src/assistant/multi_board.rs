@@ -0,0 +1,251 @@
+use crate::{
+    assistant::AssistantError,
+    solver::{Knowledge, MultiBoardSolver},
+    Attempt, Dict,
+};
+use itertools::Itertools;
+use std::io::{BufRead, Write};
+
+pub struct MultiBoardAssistant<'a> {
+    dict: &'a Dict,
+    history: Vec<Vec<Attempt>>,
+    solver: MultiBoardSolver<'a>,
+}
+
+impl<'a> MultiBoardAssistant<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict, boards: usize) -> Self {
+        Self::with_solver(dict, MultiBoardSolver::new(dict, boards))
+    }
+
+    pub fn with_solver(dict: &'a Dict, solver: MultiBoardSolver<'a>) -> Self {
+        Self {
+            dict,
+            history: vec![],
+            solver,
+        }
+    }
+
+    #[inline]
+    pub fn history(&self) -> &[Vec<Attempt>] {
+        &self.history
+    }
+
+    #[inline]
+    pub fn solver(&self) -> &MultiBoardSolver<'a> {
+        &self.solver
+    }
+
+    // a row per unsolved board, all for the same guess
+    pub fn observe(&mut self, attempts: Vec<Attempt>) -> Result<(), AssistantError> {
+        if attempts.len() != self.solver.unsolved().len() {
+            return Err(AssistantError::BoardCountMismatch(
+                self.solver.unsolved().len(),
+            ));
+        }
+        if !attempts.iter().map(Attempt::word).all_equal() {
+            return Err(AssistantError::GuessMismatch);
+        }
+        let word_len = self.dict.words().first().map(|word| word.chars().count());
+        if attempts
+            .iter()
+            .any(|Attempt(attempt_chars)| word_len.map_or(false, |len| len != attempt_chars.len()))
+        {
+            return Err(AssistantError::InputLengthMismatch);
+        }
+        if self
+            .solver
+            .unsolved()
+            .into_iter()
+            .zip(&attempts)
+            .any(|(board, attempt)| {
+                Knowledge::from_attempts([attempt])
+                    .filter_dict(self.solver.candidates(board))
+                    .words()
+                    .is_empty()
+            })
+        {
+            return Err(AssistantError::NoCandidates);
+        }
+        self.solver.observe(&attempts);
+        self.history.push(attempts);
+        Ok(())
+    }
+
+    pub fn undo(&mut self) -> Result<Vec<Attempt>, AssistantError> {
+        let attempts = self.history.pop().ok_or(AssistantError::NothingToUndo)?;
+        self.solver.start();
+        for attempts in &self.history {
+            self.solver.observe(attempts);
+        }
+        Ok(attempts)
+    }
+
+    #[inline]
+    pub fn suggest(&self) -> Option<&'static str> {
+        self.solver.suggest()
+    }
+
+    fn write_status(&self, w: &mut impl Write) -> Result<(), AssistantError> {
+        let boards = (0..self.solver.boards())
+            .map(|board| {
+                if self.solver.is_solved(board) {
+                    "solved".to_string()
+                } else {
+                    self.solver.candidates(board).words().len().to_string()
+                }
+            })
+            .join(", ");
+        match self.suggest() {
+            Some(word) => writeln!(w, "Suggestion: {word} (candidates: {boards})")?,
+            None => writeln!(w, "No suggestion (candidates: {boards})")?,
+        }
+        Ok(())
+    }
+
+    pub fn main_loop(
+        &mut self,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<(), AssistantError> {
+        let mut lines = r.lines();
+        self.write_status(w)?;
+        let mut rows = vec![];
+        loop {
+            let unsolved = self.solver.unsolved();
+            let Some(&board) = unsolved.get(rows.len()) else {
+                writeln!(w, "Solved!")?;
+                return Ok(());
+            };
+            write!(
+                w,
+                "Enter guess with feedback for board {} (/undo, /list): ",
+                board + 1
+            )?;
+            w.flush()?;
+
+            let Some(line) = lines.next() else {
+                writeln!(w)?;
+                return Ok(());
+            };
+            match line?.to_lowercase().trim() {
+                "" => {}
+                "/undo" => match rows.pop() {
+                    // only the last row of the turn being entered
+                    Some(attempt) => writeln!(w, "Undone: {attempt}")?,
+                    None => match self.undo() {
+                        Ok(attempts) => {
+                            writeln!(w, "Undone: {}", attempts.iter().join(", "))?;
+                            self.write_status(w)?;
+                        }
+                        Err(err) => writeln!(w, "{err}")?,
+                    },
+                },
+                "/list" => writeln!(
+                    w,
+                    "{}",
+                    self.solver.candidates(board).words().iter().join(" ")
+                )?,
+                line => match Attempt::parse_compact(line) {
+                    Ok(attempt) => {
+                        rows.push(attempt);
+                        if rows.len() < unsolved.len() {
+                            continue;
+                        }
+                        match self.observe(rows.split_off(0)) {
+                            Ok(()) if self.solver.is_finished() => {
+                                writeln!(w, "Solved!")?;
+                                return Ok(());
+                            }
+                            Ok(()) => self.write_status(w)?,
+                            Err(err) => writeln!(w, "{err}")?,
+                        }
+                    }
+                    Err(err) => writeln!(w, "{}", AssistantError::from(err))?,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CharPositions;
+    use std::io::Cursor;
+
+    fn dict() -> Dict {
+        Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"])
+    }
+
+    #[test]
+    fn observe() {
+        let dict = dict();
+        let mut assistant = MultiBoardAssistant::new(&dict, 2);
+        let row = |guess, answer| Attempt::evaluate(guess, &CharPositions::new(answer));
+        assert_eq!(
+            assistant.observe(vec![row("казан", "казан")]),
+            Err(AssistantError::BoardCountMismatch(2))
+        );
+        assert_eq!(
+            assistant.observe(vec![row("казан", "казан"), row("сазан", "казан")]),
+            Err(AssistantError::GuessMismatch)
+        );
+        assert_eq!(
+            assistant.observe(vec![
+                row("казан", "казан"),
+                Attempt::parse_compact("к+а+з+а+н+").unwrap()
+            ]),
+            Ok(())
+        );
+        assert!(assistant.solver().is_finished());
+        assert_eq!(assistant.undo().map(|attempts| attempts.len()), Ok(2));
+        assert_eq!(
+            assistant.observe(vec![row("казан", "казан"), row("казан", "сазан")]),
+            Ok(())
+        );
+        assert_eq!(
+            assistant.observe(vec![Attempt::parse_compact("ф+а+з+а+н").unwrap()]),
+            Err(AssistantError::NoCandidates)
+        );
+        assert_eq!(assistant.history().len(), 1);
+        assert_eq!(assistant.solver().unsolved(), [1]);
+    }
+
+    #[test]
+    fn main_loop() {
+        let dict = dict();
+        let mut assistant = MultiBoardAssistant::new(&dict, 2);
+        let mut out = vec![];
+        let mut inp = Cursor::new(
+            "к+а+з+а+н+\n/undo\n/undo\nк+а+з+а+н+\nка+з+а+н+\n/undo\n\
+            к+а+з+а+н+\nка+з+а+н+\n/list\nкасска\nс+а+з+а+н+\n",
+        );
+        assert_eq!(assistant.main_loop(&mut inp, &mut out), Ok(()));
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "\
+            Suggestion: каска (candidates: 5, 5)\n\
+            Enter guess with feedback for board 1 (/undo, /list): \
+            Enter guess with feedback for board 2 (/undo, /list): \
+            Undone: к+а+з+а+н+\n\
+            Enter guess with feedback for board 1 (/undo, /list): \
+            Nothing to undo\n\
+            Enter guess with feedback for board 1 (/undo, /list): \
+            Enter guess with feedback for board 2 (/undo, /list): \
+            Suggestion: сазан (candidates: solved, 2)\n\
+            Enter guess with feedback for board 2 (/undo, /list): \
+            Undone: к+а+з+а+н+, к а+з+а+н+\n\
+            Suggestion: каска (candidates: 5, 5)\n\
+            Enter guess with feedback for board 1 (/undo, /list): \
+            Enter guess with feedback for board 2 (/undo, /list): \
+            Suggestion: сазан (candidates: solved, 2)\n\
+            Enter guess with feedback for board 2 (/undo, /list): \
+            сазан фазан\n\
+            Enter guess with feedback for board 2 (/undo, /list): \
+            Input string length not matched to word\n\
+            Enter guess with feedback for board 2 (/undo, /list): Solved!\n\
+            "
+        );
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
 };
 use std::fmt::{Display, Formatter};
 
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
 pub struct AttemptChar {
     pub ch: char,
     pub state: CharResult,
@@ -1,7 +1,7 @@
 use crate::attempt::error::ParseAttemptError;
 use std::fmt::{Display, Formatter};
 
-#[derive(Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Eq, PartialEq, Hash, Debug, Copy, Clone)]
 pub enum CharResult {
     Exact,
     NotInPosition,
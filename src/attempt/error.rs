@@ -6,12 +6,22 @@ use std::{
 #[derive(Eq, PartialEq, Debug)]
 pub enum ParseAttemptError {
     CharResultUnexpected(char),
+    JottoCountsUnexpected(String),
+    JottoWordUnexpected(String),
+    JottoCountsExceedLength(String),
 }
 
 impl Display for ParseAttemptError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::CharResultUnexpected(ch) => write!(f, "Unexpected char result: '{ch}'"),
+            Self::JottoCountsUnexpected(counts) => {
+                write!(f, "Unexpected jotto counts: '{counts}'")
+            }
+            Self::JottoWordUnexpected(word) => write!(f, "Unexpected jotto word: '{word}'"),
+            Self::JottoCountsExceedLength(counts) => {
+                write!(f, "Jotto counts '{counts}' exceed the word length")
+            }
         }
     }
 }
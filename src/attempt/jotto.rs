@@ -0,0 +1,214 @@
+use crate::{
+    attempt::{error::ParseAttemptError, Attempt, AttemptError, CharResult},
+    CharPos, CharPositions, Dict,
+};
+use itertools::Itertools;
+use std::{
+    cmp::min,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct JottoAttempt {
+    pub word: String,
+    pub exact: usize,
+    pub misplaced: usize,
+}
+
+impl FromStr for JottoAttempt {
+    type Err = ParseAttemptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let word = parts.next().unwrap_or_default().to_string();
+        let counts = parts.collect::<String>();
+        let (exact, misplaced) = counts
+            .strip_suffix('?')
+            .and_then(|counts| counts.split_once('+'))
+            .and_then(|(exact, misplaced)| Some((exact.parse().ok()?, misplaced.parse().ok()?)))
+            .ok_or_else(|| ParseAttemptError::JottoCountsUnexpected(counts.clone()))?;
+        if !word.chars().all(char::is_alphabetic) {
+            return Err(ParseAttemptError::JottoWordUnexpected(word));
+        }
+        // both counts are of the word's letters, so together they can't be more
+        if exact + misplaced > word.chars().count() {
+            return Err(ParseAttemptError::JottoCountsExceedLength(counts));
+        }
+        Ok(Self {
+            word,
+            exact,
+            misplaced,
+        })
+    }
+}
+
+impl From<&Attempt> for JottoAttempt {
+    fn from(value: &Attempt) -> Self {
+        let Attempt(attempt_chars) = value;
+        Self {
+            word: value.word(),
+            exact: attempt_chars
+                .iter()
+                .filter(|ac| ac.state == CharResult::Exact)
+                .count(),
+            misplaced: attempt_chars
+                .iter()
+                .filter(|ac| ac.state == CharResult::NotInPosition)
+                .count(),
+        }
+    }
+}
+
+impl JottoAttempt {
+    pub fn inspect_input(
+        input: &str,
+        char_positions: &CharPositions,
+        dict: &Dict,
+    ) -> Result<Self, AttemptError> {
+        if input.chars().count() != char_positions.word_len() {
+            Err(AttemptError::InputLengthMismatch)
+        } else if !dict.word_in_dict(input) {
+            Err(AttemptError::WordNotInDict)
+        } else {
+            Ok(Self::evaluate(input, char_positions))
+        }
+    }
+
+    pub fn evaluate(input: &str, char_positions: &CharPositions) -> Self {
+        let exact = input
+            .chars()
+            .enumerate()
+            .filter(|&(pos, ch)| {
+                matches!(char_positions.positions(ch), Some(positions) if positions.contains(&CharPos(pos)))
+            })
+            .count();
+        let common: usize = input
+            .chars()
+            .counts()
+            .into_iter()
+            .filter_map(|(ch, count)| {
+                char_positions
+                    .positions(ch)
+                    .map(|positions| min(count, positions.len()))
+            })
+            .sum();
+        Self {
+            word: input.to_string(),
+            exact,
+            misplaced: common - exact,
+        }
+    }
+
+    #[inline]
+    pub fn is_win_attempt(&self) -> bool {
+        self.exact == self.word.chars().count()
+    }
+}
+
+impl Display for JottoAttempt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}+{}?", self.word, self.exact, self.misplaced)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_jotto_attempt() {
+        let attempt = JottoAttempt {
+            word: "казна".to_string(),
+            exact: 2,
+            misplaced: 2,
+        };
+        assert_eq!("казна 2+2?", attempt.to_string());
+    }
+
+    #[test]
+    fn inspect_input() {
+        assert_eq!(
+            JottoAttempt::inspect_input("казна", &"сазан".into(), &Dict::default()),
+            Ok(JottoAttempt {
+                word: "казна".to_string(),
+                exact: 2,
+                misplaced: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn inspect_input_same_letter() {
+        assert_eq!(
+            JottoAttempt::inspect_input("парад", &"парус".into(), &Dict::default()),
+            Ok(JottoAttempt {
+                word: "парад".to_string(),
+                exact: 3,
+                misplaced: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn inspect_input_not_in_dict() {
+        assert_eq!(
+            JottoAttempt::inspect_input("сазае", &"сазан".into(), &Dict::default()),
+            Err(AttemptError::WordNotInDict)
+        );
+    }
+
+    #[test]
+    fn from_attempt() {
+        assert_eq!(
+            JottoAttempt::from(&"к а+з+н?а?".parse::<Attempt>().unwrap()),
+            JottoAttempt {
+                word: "казна".to_string(),
+                exact: 2,
+                misplaced: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn is_win_attempt() {
+        assert!(JottoAttempt::evaluate("сазан", &"сазан".into()).is_win_attempt());
+        assert!(!JottoAttempt::evaluate("казан", &"сазан".into()).is_win_attempt());
+    }
+
+    #[test]
+    fn try_from() {
+        assert_eq!(
+            "казна 2+2?".parse(),
+            Ok(JottoAttempt {
+                word: "казна".to_string(),
+                exact: 2,
+                misplaced: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn try_from_err() {
+        assert_eq!(
+            "казна 2?".parse(),
+            Err::<JottoAttempt, _>(ParseAttemptError::JottoCountsUnexpected("2?".to_string()))
+        );
+        assert_eq!(
+            "каз+на 2+2?".parse(),
+            Err::<JottoAttempt, _>(ParseAttemptError::JottoWordUnexpected("каз+на".to_string()))
+        );
+        assert_eq!(
+            "казна 9+9?".parse(),
+            Err::<JottoAttempt, _>(ParseAttemptError::JottoCountsExceedLength(
+                "9+9?".to_string()
+            ))
+        );
+        assert_eq!(
+            "казна 3+3?".parse(),
+            Err::<JottoAttempt, _>(ParseAttemptError::JottoCountsExceedLength(
+                "3+3?".to_string()
+            ))
+        );
+    }
+}
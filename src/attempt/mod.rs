@@ -1,12 +1,16 @@
 mod attempt_char;
 mod char_result;
 mod error;
+mod jotto;
+mod pattern;
 
 pub use attempt_char::AttemptChar;
 pub use char_result::CharResult;
-pub use error::AttemptError;
+pub use error::{AttemptError, ParseAttemptError};
+pub use jotto::JottoAttempt;
+pub use pattern::Pattern;
 
-use crate::{attempt::error::ParseAttemptError, CharPos, CharPositions, Dict};
+use crate::{CharPos, CharPositions, Dict};
 use itertools::Itertools;
 use std::{
     fmt::{Display, Formatter},
@@ -14,7 +18,7 @@ use std::{
     str::FromStr,
 };
 
-#[derive(Eq, PartialEq, Debug, Clone)]
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
 pub struct Attempt(pub Vec<AttemptChar>);
 
 impl FromStr for Attempt {
@@ -39,55 +43,81 @@ impl FromStr for Attempt {
 }
 
 impl Attempt {
+    pub fn parse_compact(s: &str) -> Result<Self, ParseAttemptError> {
+        let mut chars = s.chars().peekable();
+        let mut attempt_chars = vec![];
+        while let Some(ch) = chars.next() {
+            if ch.is_whitespace() {
+                continue;
+            } else if CharResult::try_from(ch).is_ok() {
+                return Err(ParseAttemptError::CharResultUnexpected(ch));
+            }
+            let state = chars
+                .next_if(|&state| CharResult::try_from(state).is_ok())
+                .unwrap_or(' ');
+            attempt_chars.push((ch, state).try_into()?);
+        }
+        Ok(Self(attempt_chars))
+    }
+
     pub fn inspect_input(
         input: &str,
         char_positions: &CharPositions,
         dict: &Dict,
     ) -> Result<Self, AttemptError> {
-        let chars = input.chars().collect_vec();
-        if chars.len() != char_positions.word_len() {
+        if input.chars().count() != char_positions.word_len() {
             Err(AttemptError::InputLengthMismatch)
         } else if !dict.word_in_dict(input) {
             Err(AttemptError::WordNotInDict)
         } else {
-            let mut char_positions = char_positions.clone();
-            let exact_chars = chars
-                .iter()
+            Ok(Self::evaluate(input, char_positions))
+        }
+    }
+
+    pub fn evaluate(input: &str, char_positions: &CharPositions) -> Self {
+        let chars = input.chars().collect_vec();
+        let mut char_positions = char_positions.clone();
+        let exact_chars = chars
+            .iter()
+            .enumerate()
+            .map(|(pos, &ch)| (CharPos(pos), ch))
+            .map(
+                |(pos, ch)| match AttemptChar::test_char(&char_positions, ch, pos) {
+                    attempt_char @ AttemptChar {
+                        state: CharResult::Exact,
+                        ..
+                    } => {
+                        char_positions.remove_char_at_pos(ch, pos);
+                        Some(attempt_char)
+                    }
+                    _ => None,
+                },
+            )
+            .collect_vec();
+        Self(
+            zip(chars, exact_chars)
                 .enumerate()
-                .map(|(pos, &ch)| (CharPos(pos), ch))
-                .map(
-                    |(pos, ch)| match AttemptChar::test_char(&char_positions, ch, pos) {
+                .map(|(pos, t)| (CharPos(pos), t))
+                .map(|(pos, (ch, exact_char))| match exact_char {
+                    None => match AttemptChar::test_char(&char_positions, ch, pos) {
                         attempt_char @ AttemptChar {
-                            state: CharResult::Exact,
+                            state: CharResult::Unsuccessful,
                             ..
-                        } => {
-                            char_positions.remove_char_at_pos(ch, pos);
-                            Some(attempt_char)
+                        } => attempt_char,
+                        attempt_char => {
+                            char_positions.remove_any_pos(ch);
+                            attempt_char
                         }
-                        _ => None,
                     },
-                )
-                .collect_vec();
-            Ok(Self(
-                zip(chars, exact_chars)
-                    .enumerate()
-                    .map(|(pos, t)| (CharPos(pos), t))
-                    .map(|(pos, (ch, exact_char))| match exact_char {
-                        None => match AttemptChar::test_char(&char_positions, ch, pos) {
-                            attempt_char @ AttemptChar {
-                                state: CharResult::Unsuccessful,
-                                ..
-                            } => attempt_char,
-                            attempt_char => {
-                                char_positions.remove_char_at_pos(ch, pos);
-                                attempt_char
-                            }
-                        },
-                        Some(exact_char) => exact_char,
-                    })
-                    .collect(),
-            ))
-        }
+                    Some(exact_char) => exact_char,
+                })
+                .collect(),
+        )
+    }
+
+    pub fn word(&self) -> String {
+        let Self(attempt_chars) = self;
+        attempt_chars.iter().map(|ac| ac.ch).collect()
     }
 
     #[inline]
@@ -230,6 +260,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn word() {
+        assert_eq!(
+            "с+а+з?а е ".parse::<Attempt>().unwrap().word(),
+            "сазае".to_string()
+        );
+    }
+
     #[test]
     fn try_from() {
         assert_eq!(
@@ -288,6 +326,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_compact() {
+        assert_eq!(Attempt::parse_compact("с+а?зан+"), "с+а?з а н+".parse());
+        assert_eq!(Attempt::parse_compact("с+а?з а н+ "), "с+а?з а н+".parse());
+    }
+
+    #[test]
+    fn parse_compact_err() {
+        assert_eq!(
+            Attempt::parse_compact("с++"),
+            Err(ParseAttemptError::CharResultUnexpected('+'))
+        );
+    }
+
     #[test]
     fn try_from_err() {
         assert_eq!(
@@ -0,0 +1,207 @@
+use crate::attempt::{Attempt, AttemptChar, CharResult, ParseAttemptError};
+use itertools::Itertools;
+use std::iter::zip;
+
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Copy, Clone)]
+pub struct Pattern(pub u16);
+
+impl Pattern {
+    // 3^10 codes still fit into u16
+    pub const MAX_WORD_LEN: usize = 10;
+
+    #[inline]
+    pub fn count(word_len: usize) -> usize {
+        3usize.pow(word_len as u32)
+    }
+
+    #[inline]
+    pub fn win(word_len: usize) -> Self {
+        Self(Self::count(word_len) as u16 - 1)
+    }
+
+    #[inline]
+    pub fn index(self) -> usize {
+        let Self(code) = self;
+        code as usize
+    }
+
+    pub fn evaluate(guess: &[char], answer: &[char]) -> Self {
+        let mut exact = 0u32;
+        for (pos, (guess_ch, answer_ch)) in zip(guess, answer).enumerate() {
+            if guess_ch == answer_ch {
+                exact |= 1 << pos;
+            }
+        }
+        let mut used = exact;
+        let mut misplaced = 0u32;
+        for (pos, guess_ch) in guess.iter().enumerate() {
+            if exact & (1 << pos) != 0 {
+                continue;
+            }
+            let answer_pos = (0..answer.len()).position(|answer_pos| {
+                used & (1 << answer_pos) == 0 && answer[answer_pos] == *guess_ch
+            });
+            if let Some(answer_pos) = answer_pos {
+                used |= 1 << answer_pos;
+                misplaced |= 1 << pos;
+            }
+        }
+        Self::from_states((0..guess.len()).map(|pos| {
+            if exact & (1 << pos) != 0 {
+                CharResult::Exact
+            } else if misplaced & (1 << pos) != 0 {
+                CharResult::NotInPosition
+            } else {
+                CharResult::Unsuccessful
+            }
+        }))
+    }
+
+    pub fn from_states(states: impl DoubleEndedIterator<Item = CharResult>) -> Self {
+        Self(states.rev().fold(0, |acc, state| {
+            acc * 3
+                + match state {
+                    CharResult::Unsuccessful => 0,
+                    CharResult::NotInPosition => 1,
+                    CharResult::Exact => 2,
+                }
+        }))
+    }
+
+    pub fn states(self, word_len: usize) -> Vec<CharResult> {
+        let Self(mut code) = self;
+        (0..word_len)
+            .map(|_| {
+                let state = match code % 3 {
+                    0 => CharResult::Unsuccessful,
+                    1 => CharResult::NotInPosition,
+                    _ => CharResult::Exact,
+                };
+                code /= 3;
+                state
+            })
+            .collect()
+    }
+
+    // shared results use 🟩🟨⬜ or ⬛, high contrast ones 🟧🟦
+    pub fn from_emoji(row: &str) -> Result<Self, ParseAttemptError> {
+        let states: Vec<_> = row
+            .chars()
+            .filter(|&ch| !ch.is_whitespace() && ch != '\u{fe0f}')
+            .map(|ch| match ch {
+                '🟩' | '🟧' => Ok(CharResult::Exact),
+                '🟨' | '🟦' => Ok(CharResult::NotInPosition),
+                '⬜' | '⬛' => Ok(CharResult::Unsuccessful),
+                ch => Err(ParseAttemptError::CharResultUnexpected(ch)),
+            })
+            .try_collect()?;
+        Ok(Self::from_states(states.into_iter()))
+    }
+
+    pub fn to_emoji(self, word_len: usize) -> String {
+        self.states(word_len)
+            .into_iter()
+            .map(|state| match state {
+                CharResult::Exact => '🟩',
+                CharResult::NotInPosition => '🟨',
+                CharResult::Unsuccessful => '⬜',
+            })
+            .collect()
+    }
+
+    pub fn to_attempt(self, guess: &str) -> Attempt {
+        let chars = guess.chars().collect::<Vec<_>>();
+        Attempt(
+            zip(&chars, self.states(chars.len()))
+                .map(|(&ch, state)| AttemptChar { ch, state })
+                .collect(),
+        )
+    }
+}
+
+impl From<&Attempt> for Pattern {
+    fn from(value: &Attempt) -> Self {
+        let Attempt(attempt_chars) = value;
+        Self::from_states(attempt_chars.iter().map(|ac| ac.state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dict;
+
+    fn evaluate(guess: &str, answer: &str) -> Pattern {
+        Pattern::evaluate(&guess.chars().collect_vec(), &answer.chars().collect_vec())
+    }
+
+    #[test]
+    fn evaluate_codes() {
+        assert_eq!(evaluate("сазан", "сазан"), Pattern(242));
+        assert_eq!(evaluate("сазан", "сазан"), Pattern::win(5));
+        assert_eq!(evaluate("сазан", "фикус"), Pattern(1));
+        assert_eq!(evaluate("нотка", "сазан"), Pattern(1 + 3 * 3 * 3 * 3));
+    }
+
+    #[test]
+    fn from_emoji() {
+        assert_eq!(Pattern::from_emoji("🟩🟩🟩🟩🟩"), Ok(Pattern::win(5)));
+        assert_eq!(
+            Pattern::from_emoji("⬜🟨⬛️🟩 ⬜"),
+            Ok(Pattern::from(&"к а?з а+н ".parse::<Attempt>().unwrap()))
+        );
+        assert_eq!(Pattern::from_emoji("🟦🟧"), Pattern::from_emoji("🟨🟩"));
+        assert_eq!(
+            Pattern::from_emoji("🟩🟥"),
+            Err(ParseAttemptError::CharResultUnexpected('🟥'))
+        );
+        assert_eq!(evaluate("нотка", "сазан").to_emoji(5), "🟨⬜⬜⬜🟨");
+    }
+
+    #[test]
+    fn evaluate_same_as_attempt() {
+        let dict = Dict::default();
+        for (guess, answer) in dict.words().iter().step_by(97).cartesian_product(
+            dict.words()
+                .iter()
+                .step_by(89)
+                .chain(["шалаш", "парус", "налог"].iter()),
+        ) {
+            assert_eq!(
+                evaluate(guess, answer),
+                Pattern::from(&Attempt::evaluate(guess, &(*answer).into()))
+            );
+        }
+    }
+
+    #[test]
+    fn from_attempt() {
+        assert_eq!(
+            Pattern::from(&"с?а з а н+".parse::<Attempt>().unwrap()),
+            Pattern(1 + 2 * 3 * 3 * 3 * 3)
+        );
+    }
+
+    #[test]
+    fn to_attempt() {
+        let attempt = "а?г а?г о ".parse::<Attempt>().unwrap();
+        assert_eq!(Pattern::from(&attempt).to_attempt("агаго"), attempt);
+        assert_eq!(
+            evaluate("казна", "сазан").to_attempt("казна"),
+            "к а+з+н?а?".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn states() {
+        assert_eq!(
+            Pattern(1 + 2 * 3).states(3),
+            [
+                CharResult::NotInPosition,
+                CharResult::Exact,
+                CharResult::Unsuccessful
+            ]
+        );
+        assert_eq!(Pattern::count(5), 243);
+    }
+}
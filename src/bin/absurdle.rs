@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Parser;
+use mordle::{
+    solver::{self, PatternMatrix},
+    Absurdle, Dict,
+};
+use std::{
+    io,
+    io::{BufWriter, Write},
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Find the shortest sequence of guesses forcing a win against the Absurdle host",
+    long_about = None
+)]
+struct Cli {
+    /// Give up on sequences longer than this
+    #[arg(short, long, default_value_t = 8)]
+    max_guesses: usize,
+    /// Only try this many most shrinking guesses at every step: much faster, but the result is
+    /// not proven shortest
+    #[arg(short, long)]
+    breadth: Option<usize>,
+    /// Compute the feedback of every guess and answer instead of using the cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = Dict::default();
+    let matrix = if cli.no_cache {
+        PatternMatrix::from_dict(&dict)
+    } else {
+        PatternMatrix::from_dict_cached(&dict)
+    };
+    let win = solver::absurdle_win(&matrix, cli.max_guesses, cli.breadth)
+        .ok_or_else(|| anyhow::anyhow!("No forced win in {} guesses", cli.max_guesses))?;
+    if !win.verify(&dict) {
+        anyhow::bail!("Replay against the host failed: {}", win.guesses.join(" "));
+    }
+    let mut w = BufWriter::new(io::stdout().lock());
+    let mut game = Absurdle::new(&dict, win.guesses.len());
+    for guess in &win.guesses {
+        let attempt = game.try_input(guess)?.to_string();
+        writeln!(w, "{attempt} ({} left)", game.candidates().words().len())?;
+    }
+    writeln!(w, "Guesses: {}", win.guesses.len())?;
+    writeln!(
+        w,
+        "Proven shortest: {}",
+        if win.proven { "yes" } else { "no" }
+    )?;
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use anyhow::Result;
+use clap::Parser;
+use mordle::{
+    assistant::{Assistant, JottoAssistant, MultiBoardAssistant},
+    cli::{self, ProbeArg, StrategyArg},
+    solver::{FibbleSolver, MultiBoardSolver, PatternMatrix, ProbePolicy, SolverOptions, Strategy},
+    Dict,
+};
+use std::{
+    io,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+    time::Duration,
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Suggest guesses for an external game: enter each guess with its feedback, \
+    e.g. 'с+а?зан+' ('+' is exact, '?' is not in position, no mark is absent)",
+    long_about = None
+)]
+struct Cli {
+    /// Strategy for suggestions
+    #[arg(short, long, value_enum, default_value_t = StrategyArg::Frequency)]
+    strategy: StrategyArg,
+    /// Only suggest words keeping revealed exact and misplaced letters
+    #[arg(long, default_value_t = false)]
+    hard_mode: bool,
+    /// When to guess words which can't be the answer to split the candidates
+    #[arg(long, value_enum, default_value_t = ProbeArg::Auto)]
+    probe: ProbeArg,
+    /// Only probe while more than this many candidates remain
+    #[arg(long, conflicts_with = "probe")]
+    probe_above: Option<usize>,
+    /// Seconds to search for a guess with the anytime strategy
+    #[arg(long, value_parser = cli::parse_seconds)]
+    time_budget: Option<Duration>,
+    /// Dictionary file: a word per line, optionally followed by its frequency
+    #[arg(short, long)]
+    dict: Option<PathBuf>,
+    /// Compute the feedback of every guess and answer instead of using the cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+    /// Number of boards played at once, e.g. 2 for Dordle or 4 for Quordle; several boards are
+    /// solved by combined entropy and ignore the strategy options
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..))]
+    boards: u8,
+    /// Play against a host lying about one tile per row with this probability, 1 for Fibble;
+    /// ignores the strategy options
+    #[arg(long, conflicts_with = "boards")]
+    lie_probability: Option<f64>,
+    /// Play Jotto: enter each guess with the counts of exact and misplaced letters,
+    /// e.g. 'казна 2+2?'; ignores the strategy options
+    #[arg(long, default_value_t = false, conflicts_with_all = ["boards", "lie_probability"])]
+    jotto: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = match cli.dict {
+        Some(path) => Dict::load(path)?,
+        None => Dict::default(),
+    };
+    if cli.jotto {
+        JottoAssistant::new(&dict).main_loop(
+            &mut BufReader::new(io::stdin().lock()),
+            &mut BufWriter::new(io::stdout().lock()),
+        )?;
+        return Ok(());
+    }
+    if cli.boards > 1 {
+        let boards = usize::from(cli.boards);
+        let solver = if cli.no_cache {
+            MultiBoardSolver::new(&dict, boards)
+        } else {
+            MultiBoardSolver::with_matrix(&dict, boards, PatternMatrix::from_dict_cached(&dict))?
+        };
+        MultiBoardAssistant::with_solver(&dict, solver).main_loop(
+            &mut BufReader::new(io::stdin().lock()),
+            &mut BufWriter::new(io::stdout().lock()),
+        )?;
+        return Ok(());
+    }
+    if let Some(lie_probability) = cli.lie_probability {
+        if !(0.0..=1.0).contains(&lie_probability) {
+            anyhow::bail!("Lie probability should be between 0 and 1");
+        }
+        let solver = if cli.no_cache {
+            FibbleSolver::with_lie_probability(&dict, lie_probability)
+        } else {
+            FibbleSolver::with_matrix(
+                &dict,
+                lie_probability,
+                PatternMatrix::from_dict_cached(&dict),
+            )?
+        };
+        Assistant::with_solver(&dict, Box::new(solver)).main_loop(
+            &mut BufReader::new(io::stdin().lock()),
+            &mut BufWriter::new(io::stdout().lock()),
+        )?;
+        return Ok(());
+    }
+    let strategy = Strategy::from(cli.strategy);
+    let options = SolverOptions {
+        hard_mode: cli.hard_mode,
+        probe: cli
+            .probe_above
+            .map_or(cli.probe.into(), ProbePolicy::MoreThan),
+        time_budget: cli.time_budget,
+    };
+    let solver = if cli.no_cache || !strategy.uses_matrix() {
+        strategy.solver_with_options(&dict, options)
+    } else {
+        strategy.solver_with_matrix(&dict, options, PatternMatrix::from_dict_cached(&dict))?
+    };
+    Assistant::with_solver(&dict, solver).main_loop(
+        &mut BufReader::new(io::stdin().lock()),
+        &mut BufWriter::new(io::stdout().lock()),
+    )?;
+    Ok(())
+}
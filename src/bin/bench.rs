@@ -0,0 +1,232 @@
+use anyhow::Result;
+use clap::Parser;
+use mordle::{
+    cli::{self, ProbeArg, StrategyArg},
+    solver::{self, BenchReport, PatternMatrix, ProbePolicy, SolverOptions, Strategy},
+    Dict,
+};
+use std::{
+    io,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Play every word of the dictionary with a solver and report the number of guesses",
+    long_about = None
+)]
+struct Cli {
+    /// Strategy for guesses
+    #[arg(short, long, value_enum, default_value_t = StrategyArg::Frequency)]
+    strategy: StrategyArg,
+    /// Only guess words keeping revealed exact and misplaced letters
+    #[arg(long, default_value_t = false)]
+    hard_mode: bool,
+    /// When to guess words which can't be the answer to split the candidates
+    #[arg(long, value_enum, default_value_t = ProbeArg::Auto)]
+    probe: ProbeArg,
+    /// Only probe while more than this many candidates remain
+    #[arg(long, conflicts_with = "probe")]
+    probe_above: Option<usize>,
+    /// Seconds to search for a guess with the anytime strategy
+    #[arg(long, value_parser = cli::parse_seconds)]
+    time_budget: Option<Duration>,
+    /// Dictionary file: a word per line, optionally followed by its frequency
+    #[arg(short, long)]
+    dict: Option<PathBuf>,
+    /// Compute the feedback of every guess and answer instead of using the cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+    /// Tries per word, the word is failed after them
+    #[arg(short, long, default_value_t = 6)]
+    tries: usize,
+    /// Number of worst words to print
+    #[arg(short, long, default_value_t = 10)]
+    worst: usize,
+    /// Report words differing in a single position for families of at least this size
+    #[arg(long, default_value_t = 7)]
+    trap_size: usize,
+    /// Print a CSV line per word instead of the summary
+    #[arg(long, default_value_t = false)]
+    csv: bool,
+    /// Play every word both normally and in hard mode, and report how much hard mode costs
+    #[arg(long, default_value_t = false, conflicts_with_all = ["hard_mode", "csv"])]
+    compare_hard_mode: bool,
+}
+
+// "normal / hard (delta)", without the delta when either side has no value
+fn change(normal: Option<String>, hard: Option<String>, delta: Option<String>) -> String {
+    let value = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    match delta {
+        Some(delta) => format!("{} / {} ({delta})", value(normal), value(hard)),
+        None => format!("{} / {}", value(normal), value(hard)),
+    }
+}
+
+fn average_change(normal: &BenchReport, hard: &BenchReport) -> String {
+    let (normal, hard) = (normal.average(), hard.average());
+    change(
+        normal.map(|average| format!("{average:.4}")),
+        hard.map(|average| format!("{average:.4}")),
+        normal
+            .zip(hard)
+            .map(|(normal, hard)| format!("{:+.4}", hard - normal)),
+    )
+}
+
+fn failed_change(normal: &BenchReport, hard: &BenchReport) -> String {
+    let (normal, hard) = (normal.failed(), hard.failed());
+    change(
+        Some(normal.to_string()),
+        Some(hard.to_string()),
+        Some(format!("{:+}", hard as isize - normal as isize)),
+    )
+}
+
+fn worst_change(normal: &BenchReport, hard: &BenchReport) -> String {
+    let (normal, hard) = (normal.worst_case(), hard.worst_case());
+    change(
+        normal.map(|worst| worst.to_string()),
+        hard.map(|worst| worst.to_string()),
+        normal
+            .zip(hard)
+            .map(|(normal, hard)| format!("{:+}", hard as isize - normal as isize)),
+    )
+}
+
+fn write_comparison(
+    w: &mut impl Write,
+    dict: &Dict,
+    normal: &BenchReport,
+    hard: &BenchReport,
+    trap_size: usize,
+) -> io::Result<()> {
+    writeln!(w, "Words: {}", normal.games.len())?;
+    writeln!(w, "Normal / hard mode:")?;
+    writeln!(w, "Average guesses: {}", average_change(normal, hard))?;
+    writeln!(w, "Failed: {}", failed_change(normal, hard))?;
+    writeln!(w, "Worst case: {}", worst_change(normal, hard))?;
+    let families = solver::trap_families(dict, trap_size);
+    if !families.is_empty() {
+        writeln!(w, "Trap families:")?;
+        for family in families {
+            let (normal, hard) = (normal.subset(&family.words), hard.subset(&family.words));
+            writeln!(
+                w,
+                "  {} ({} words): failed {}, worst {}",
+                family.pattern,
+                family.words.len(),
+                failed_change(&normal, &hard),
+                worst_change(&normal, &hard)
+            )?;
+        }
+    }
+    writeln!(
+        w,
+        "Time: {:.3}s / {:.3}s",
+        normal.elapsed.as_secs_f64(),
+        hard.elapsed.as_secs_f64()
+    )?;
+    Ok(())
+}
+
+fn write_summary(
+    w: &mut impl Write,
+    dict: &Dict,
+    report: &BenchReport,
+    worst: usize,
+    trap_size: usize,
+) -> io::Result<()> {
+    writeln!(w, "Words: {}", report.games.len())?;
+    writeln!(w, "Solved: {}", report.solved())?;
+    writeln!(w, "Failed: {}", report.failed())?;
+    match report.average() {
+        Some(average) => writeln!(w, "Average guesses: {average:.4}")?,
+        None => writeln!(w, "Average guesses: -")?,
+    }
+    writeln!(w, "Distribution:")?;
+    for (i, count) in report.distribution().into_iter().enumerate() {
+        writeln!(w, "  {}: {count}", i + 1)?;
+    }
+    writeln!(w, "  failed: {}", report.failed())?;
+    writeln!(w, "Worst words:")?;
+    for game in report.worst(worst) {
+        writeln!(
+            w,
+            "  {} ({}): {}",
+            game.secret,
+            if game.solved {
+                game.guesses.len().to_string()
+            } else {
+                "failed".to_string()
+            },
+            game.guesses.join(" ")
+        )?;
+    }
+    let families = solver::trap_families(dict, trap_size);
+    if !families.is_empty() {
+        writeln!(w, "Trap families:")?;
+        for family in families {
+            let games = report.subset(&family.words);
+            writeln!(
+                w,
+                "  {} ({} words): failed {}, worst {}",
+                family.pattern,
+                family.words.len(),
+                games.failed(),
+                games
+                    .worst_case()
+                    .map_or("-".to_string(), |worst| worst.to_string())
+            )?;
+        }
+    }
+    let per_word = report.elapsed.as_secs_f64() * 1000.0 / report.games.len().max(1) as f64;
+    writeln!(
+        w,
+        "Time: {:.3}s ({per_word:.3}ms per word)",
+        report.elapsed.as_secs_f64()
+    )?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = match cli.dict {
+        Some(path) => Dict::load(path)?,
+        None => Dict::default(),
+    };
+    let options = SolverOptions {
+        hard_mode: cli.hard_mode,
+        probe: cli
+            .probe_above
+            .map_or(cli.probe.into(), ProbePolicy::MoreThan),
+        time_budget: cli.time_budget,
+    };
+    let strategy = Strategy::from(cli.strategy);
+    let matrix =
+        (!cli.no_cache && strategy.uses_matrix()).then(|| PatternMatrix::from_dict_cached(&dict));
+    let run = |options| -> Result<BenchReport> {
+        let mut solver = match &matrix {
+            Some(matrix) => strategy.solver_with_matrix(&dict, options, matrix.clone())?,
+            None => strategy.solver_with_options(&dict, options),
+        };
+        Ok(solver::bench(solver.as_mut(), &dict, cli.tries))
+    };
+    let report = run(options)?;
+    let mut w = BufWriter::new(io::stdout().lock());
+    if cli.compare_hard_mode {
+        let hard = run(SolverOptions {
+            hard_mode: true,
+            ..options
+        })?;
+        write_comparison(&mut w, &dict, &report, &hard, cli.trap_size)?;
+    } else if cli.csv {
+        report.write_csv(&mut w)?;
+    } else {
+        write_summary(&mut w, &dict, &report, cli.worst, cli.trap_size)?;
+    }
+    Ok(())
+}
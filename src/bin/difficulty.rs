@@ -0,0 +1,84 @@
+use anyhow::Result;
+use clap::Parser;
+use mordle::{
+    cli::StrategyArg,
+    solver::{PatternMatrix, SolverOptions, Strategy, WordRatings},
+    Dict,
+};
+use std::{
+    io,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Rate every word of the dictionary by how hard it is to guess",
+    long_about = None
+)]
+struct Cli {
+    /// Strategies to play every word with, may be repeated
+    #[arg(short, long, value_enum, default_values_t = [StrategyArg::Frequency, StrategyArg::Entropy])]
+    strategy: Vec<StrategyArg>,
+    /// Dictionary file: a word per line, optionally followed by its frequency
+    #[arg(short, long)]
+    dict: Option<PathBuf>,
+    /// Compute the feedback of every guess and answer instead of using the cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+    /// Tries per word, the word is failed after them
+    #[arg(short, long, default_value_t = 6)]
+    tries: usize,
+    /// Number of hardest words to print
+    #[arg(short, long, default_value_t = 10)]
+    worst: usize,
+    /// Print the ratings as CSV instead of the summary, to be used by the game
+    #[arg(long, default_value_t = false)]
+    csv: bool,
+}
+
+fn write_summary(w: &mut impl Write, ratings: &WordRatings, worst: usize) -> io::Result<()> {
+    writeln!(w, "Words: {}", ratings.ratings().len())?;
+    writeln!(w, "Hardest words:")?;
+    for rating in ratings.ratings().iter().rev().take(worst) {
+        writeln!(
+            w,
+            "  {}: average {:.2}, worst {}, neighbors {}, letter rarity {:.2}",
+            rating.word, rating.average, rating.worst, rating.neighbors, rating.letter_rarity
+        )?;
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = match cli.dict {
+        Some(path) => Dict::load(path)?,
+        None => Dict::default(),
+    };
+    let strategies = cli
+        .strategy
+        .into_iter()
+        .map(Strategy::from)
+        .collect::<Vec<_>>();
+    let matrix = (!cli.no_cache && strategies.iter().any(|strategy| strategy.uses_matrix()))
+        .then(|| PatternMatrix::from_dict_cached(&dict));
+    let mut solvers = strategies
+        .into_iter()
+        .map(|strategy| match &matrix {
+            Some(matrix) if strategy.uses_matrix() => {
+                strategy.solver_with_matrix(&dict, SolverOptions::default(), matrix.clone())
+            }
+            _ => Ok(strategy.solver(&dict)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let ratings = WordRatings::rate(&dict, &mut solvers, cli.tries);
+    let mut w = BufWriter::new(io::stdout().lock());
+    if cli.csv {
+        ratings.write_csv(&mut w)?;
+    } else {
+        write_summary(&mut w, &ratings, cli.worst)?;
+    }
+    Ok(())
+}
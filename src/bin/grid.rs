@@ -0,0 +1,69 @@
+use anyhow::Result;
+use clap::Parser;
+use itertools::Itertools;
+use mordle::{puzzle, solver::PatternMatrix, Dict};
+use std::{
+    io,
+    io::{BufWriter, Read, Write},
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Read a shared grid of colored tiles from stdin and list the answers it fits, or for \
+    a known answer the guesses behind every row",
+    long_about = None
+)]
+struct Cli {
+    /// Answer of the game the grid comes from
+    #[arg(short, long)]
+    answer: Option<String>,
+    /// Number of words to print per row or for the answers
+    #[arg(short, long, default_value_t = 20)]
+    top: usize,
+    /// Compute the feedback of every guess and answer instead of using the cache
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = Dict::default();
+    let mut grid = String::new();
+    io::stdin().lock().read_to_string(&mut grid)?;
+    let word_len = dict.words().first().map_or(0, |word| word.chars().count());
+    let rows = puzzle::parse_grid(&grid, word_len)?;
+    let mut w = BufWriter::new(io::stdout().lock());
+    match cli.answer {
+        Some(answer) => {
+            let answer = answer.to_lowercase();
+            if !dict.word_in_dict(&answer) {
+                anyhow::bail!("Answer not in dictionary");
+            }
+            let guesses = puzzle::grid_guesses(&dict, &answer, &rows);
+            for (row, words) in rows.iter().zip(&guesses) {
+                writeln!(
+                    w,
+                    "{} ({}): {}",
+                    row.to_emoji(word_len),
+                    words.len(),
+                    words.iter().take(cli.top).join(" ")
+                )?;
+            }
+            if guesses.iter().any(|words| words.is_empty()) {
+                writeln!(w, "No dictionary word produces some of the rows")?;
+            }
+        }
+        None => {
+            let matrix = if cli.no_cache {
+                PatternMatrix::from_dict(&dict)
+            } else {
+                PatternMatrix::from_dict_cached(&dict)
+            };
+            let answers = puzzle::grid_answers(&matrix, &rows);
+            writeln!(w, "Answers: {}", answers.len())?;
+            writeln!(w, "{}", answers.iter().take(cli.top).join(" "))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+use mordle::{
+    solver::{self, OpenerMetric},
+    Dict,
+};
+use std::{
+    io,
+    io::{BufWriter, Write},
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Rank first guesses against the whole dictionary",
+    long_about = None
+)]
+struct Cli {
+    /// Metric to sort by
+    #[arg(short, long, value_enum, default_value_t = MetricArg::Entropy)]
+    metric: MetricArg,
+    /// Only words without repeated letters
+    #[arg(short, long, default_value_t = false)]
+    distinct_letters: bool,
+    /// Number of words to print
+    #[arg(short, long, default_value_t = 20)]
+    top: usize,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum MetricArg {
+    /// Most expected information
+    Entropy,
+    /// Fewest candidates left on average
+    ExpectedRemaining,
+    /// Fewest candidates left in the worst case
+    WorstCase,
+    /// Most frequent letters at positions
+    Frequency,
+}
+
+impl From<MetricArg> for OpenerMetric {
+    #[inline]
+    fn from(value: MetricArg) -> Self {
+        match value {
+            MetricArg::Entropy => OpenerMetric::Entropy,
+            MetricArg::ExpectedRemaining => OpenerMetric::ExpectedRemaining,
+            MetricArg::WorstCase => OpenerMetric::WorstCase,
+            MetricArg::Frequency => OpenerMetric::Frequency,
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = Dict::default();
+    let openers = solver::rank_openers(&dict, &dict, cli.metric.into(), cli.distinct_letters);
+    let mut w = BufWriter::new(io::stdout().lock());
+    writeln!(w, "word\tentropy\texpected\tworst\tfrequency")?;
+    for opener in openers.into_iter().take(cli.top) {
+        writeln!(
+            w,
+            "{}\t{:.4}\t{:.2}\t{}\t{}",
+            opener.word,
+            opener.entropy,
+            opener.expected_remaining,
+            opener.worst_case,
+            opener.frequency
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Parser;
+use itertools::Itertools;
+use mordle::{solver, Attempt, Dict};
+use std::{
+    io,
+    io::{BufWriter, Write},
+};
+
+#[derive(Parser)]
+#[command(author, version)]
+#[command(
+    about = "Show how a guess splits the candidates left after the history of attempts",
+    long_about = None
+)]
+struct Cli {
+    /// Guess to inspect
+    guess: String,
+    /// Previous guess with feedback, e.g. 'с+а?зан+' ('+' is exact, '?' is not in position, no
+    /// mark is absent)
+    #[arg(short, long)]
+    attempt: Vec<String>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let dict = Dict::default();
+    let guess = cli.guess.to_lowercase();
+    let attempts: Vec<_> = cli
+        .attempt
+        .iter()
+        .map(|attempt| Attempt::parse_compact(&attempt.to_lowercase()))
+        .try_collect()?;
+    let word_len = dict.words().first().map_or(0, |word| word.chars().count());
+    if guess.chars().count() != word_len
+        || attempts
+            .iter()
+            .any(|Attempt(attempt_chars)| attempt_chars.len() != word_len)
+    {
+        anyhow::bail!("Word length should be {word_len}");
+    }
+    let candidates = solver::candidates(&dict, &attempts);
+    let buckets = solver::partition(&guess, &candidates);
+    let mut w = BufWriter::new(io::stdout().lock());
+    writeln!(w, "Candidates: {}", candidates.words().len())?;
+    writeln!(w, "Buckets: {}", buckets.len())?;
+    for bucket in buckets {
+        writeln!(
+            w,
+            "{} ({}): {}",
+            bucket.attempt,
+            bucket.words.len(),
+            bucket.words.iter().join(" ")
+        )?;
+    }
+    Ok(())
+}
@@ -33,6 +33,15 @@ impl CharPositions {
         }
     }
 
+    // the leftmost one, to not depend on the set iteration order
+    pub fn remove_any_pos(&mut self, ch: char) {
+        if let Some(set) = self.index.get_mut(&ch) {
+            if let Some(&pos) = set.iter().min() {
+                set.remove(&pos);
+            }
+        }
+    }
+
     pub fn positions(&self, ch: char) -> Option<&HashSet<CharPos>> {
         self.index.get(&ch)
     }
@@ -71,6 +80,16 @@ mod tests {
         assert_eq!(word_index.positions('с'), Some(&HashSet::new()));
     }
 
+    #[test]
+    fn remove_any_pos() {
+        let mut word_index = CharPositions::new("сазан");
+        word_index.remove_any_pos('а');
+        assert_eq!(word_index.positions('а'), Some(&[CharPos(3)].into()));
+        word_index.remove_any_pos('а');
+        word_index.remove_any_pos('а');
+        assert_eq!(word_index.positions('а'), Some(&HashSet::new()));
+    }
+
     #[test]
     fn positions() {
         let word_index = CharPositions::new("сазан");
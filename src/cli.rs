@@ -0,0 +1,75 @@
+use crate::solver::{ProbePolicy, Strategy};
+use clap::ValueEnum;
+use std::time::{Duration, Instant};
+
+#[derive(Copy, Clone, ValueEnum)]
+pub enum StrategyArg {
+    /// Most frequent letters at positions
+    Frequency,
+    /// Most expected information
+    Entropy,
+    /// Smallest worst-case candidates left
+    Minimax,
+    /// Fewest candidates left on average, weighted by word frequency
+    ExpectedCost,
+    /// Search for the optimal guess until the time budget runs out
+    Anytime,
+}
+
+impl From<StrategyArg> for Strategy {
+    #[inline]
+    fn from(value: StrategyArg) -> Self {
+        match value {
+            StrategyArg::Frequency => Strategy::Frequency,
+            StrategyArg::Entropy => Strategy::Entropy,
+            StrategyArg::Minimax => Strategy::Minimax,
+            StrategyArg::ExpectedCost => Strategy::ExpectedCost,
+            StrategyArg::Anytime => Strategy::Anytime,
+        }
+    }
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+pub enum ProbeArg {
+    /// Only scoring strategies probe
+    Auto,
+    /// Only guess candidates
+    Never,
+    /// Guess any word
+    Always,
+}
+
+impl From<ProbeArg> for ProbePolicy {
+    #[inline]
+    fn from(value: ProbeArg) -> Self {
+        match value {
+            ProbeArg::Auto => ProbePolicy::Auto,
+            ProbeArg::Never => ProbePolicy::Never,
+            ProbeArg::Always => ProbePolicy::Always,
+        }
+    }
+}
+
+// clap value parser for a number of seconds, rejecting negative, NaN and too large values
+// which panic on conversion to `Duration` or on adding to `Instant`
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs = s.parse::<f64>().map_err(|err| err.to_string())?;
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|&duration| Instant::now().checked_add(duration).is_some())
+        .ok_or_else(|| format!("{s} is not a valid number of seconds"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_seconds() {
+        assert_eq!(super::parse_seconds("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(super::parse_seconds("0"), Ok(Duration::ZERO));
+        for s in ["-1", "NaN", "inf", "1e30", "1e19", "abc", ""] {
+            assert!(super::parse_seconds(s).is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::Pattern;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+};
+
+#[derive(Debug)]
+pub enum DictError {
+    UnexpectedColumns(usize),
+    InvalidWeight(usize, String),
+    InvalidWordLength(usize, String),
+    IoError(io::Error),
+}
+
+#[cfg(test)]
+impl PartialEq for DictError {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::UnexpectedColumns(line) => {
+                matches!(other, Self::UnexpectedColumns(other_line) if line == other_line)
+            }
+            Self::InvalidWeight(line, weight) => {
+                matches!(other, Self::InvalidWeight(other_line, other_weight)
+                    if line == other_line && weight == other_weight)
+            }
+            Self::InvalidWordLength(line, word) => {
+                matches!(other, Self::InvalidWordLength(other_line, other_word)
+                    if line == other_line && word == other_word)
+            }
+            Self::IoError(io) => {
+                matches!(other, Self::IoError(other_io) if io.kind() == other_io.kind())
+            }
+        }
+    }
+}
+
+impl Error for DictError {}
+
+impl From<io::Error> for DictError {
+    #[inline]
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl Display for DictError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedColumns(line) => {
+                write!(f, "Line {line}: expected a word and an optional frequency")
+            }
+            Self::InvalidWeight(line, weight) => {
+                write!(f, "Line {line}: invalid frequency '{weight}'")
+            }
+            Self::InvalidWordLength(line, word) => write!(
+                f,
+                "Line {line}: word '{word}' must be of the same length as the other words and \
+                at most {} letters",
+                Pattern::MAX_WORD_LEN
+            ),
+            Self::IoError(err) => write!(f, "I/O Error: {err}"),
+        }
+    }
+}
@@ -1,20 +1,34 @@
-use crate::CharPos;
+mod error;
+
+pub use error::DictError;
+
+use crate::{CharPos, Pattern};
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::Arc,
+};
 
 const DICT: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/res/mordle-dict.txt"));
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
 pub struct WordIndex(pub usize);
 
-#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Dict {
     words: Vec<&'static str>,
     words_set: HashSet<&'static str>,
     global_char_index: HashMap<char, HashSet<WordIndex>>,
     char_at_pos_index: HashMap<CharPos, HashMap<char, HashSet<WordIndex>>>,
+    // shared by all dictionaries filtered from this one
+    weights: Option<Arc<HashMap<&'static str, f64>>>,
 }
 
+// weights are never NaN: Dict::parse rejects them, and with_weights isn't public
+impl Eq for Dict {}
+
 impl Default for Dict {
     fn default() -> Self {
         let mut words = DICT.lines().collect_vec();
@@ -37,9 +51,72 @@ impl Dict {
             words_set: Default::default(),
             global_char_index: Default::default(),
             char_at_pos_index: Default::default(),
+            weights: None,
         }
     }
 
+    // one word per line with an optional frequency after whitespace,
+    // words without frequency are taken as never seen;
+    // all words must be of the same length, which must fit into a Pattern
+    pub fn parse(s: &'static str) -> Result<Self, DictError> {
+        let mut weights = HashMap::new();
+        let mut weighted = false;
+        let mut word_len = None;
+        for (line_no, line) in s.lines().enumerate() {
+            let mut columns = line.split_whitespace();
+            let (Some(word), weight, None) = (columns.next(), columns.next(), columns.next())
+            else {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(DictError::UnexpectedColumns(line_no + 1));
+            };
+            let word = if word.chars().any(char::is_uppercase) {
+                &*Box::leak(word.to_lowercase().into_boxed_str())
+            } else {
+                word
+            };
+            let len = word.chars().count();
+            if len > Pattern::MAX_WORD_LEN || *word_len.get_or_insert(len) != len {
+                return Err(DictError::InvalidWordLength(line_no + 1, word.to_string()));
+            }
+            let weight = match weight {
+                Some(weight) => {
+                    weighted = true;
+                    weight
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|weight| weight.is_finite() && *weight >= 0.0)
+                        .ok_or_else(|| DictError::InvalidWeight(line_no + 1, weight.to_string()))?
+                }
+                None => 0.0,
+            };
+            weights.entry(word).or_insert(weight);
+        }
+        let mut words = weights.keys().copied().collect_vec();
+        words.sort_unstable();
+        let dict = Self::from_words_vec(words);
+        Ok(if weighted {
+            dict.with_weights(weights)
+        } else {
+            dict
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, DictError> {
+        // the dictionary lives as long as the program, like the embedded one
+        Self::parse(Box::leak(fs::read_to_string(path)?.into_boxed_str()))
+    }
+
+    // the weights have to be finite and non-negative
+    pub(crate) fn with_weights(
+        mut self,
+        weights: impl IntoIterator<Item = (&'static str, f64)>,
+    ) -> Self {
+        self.weights = Some(Arc::new(weights.into_iter().collect()));
+        self
+    }
+
     #[cfg(test)]
     fn char_stat(words: impl IntoIterator<Item = &'static str>) -> HashMap<char, usize> {
         words
@@ -85,6 +162,14 @@ impl Dict {
             words_set,
             global_char_index,
             char_at_pos_index,
+            weights: None,
+        }
+    }
+
+    fn with_words(&self, words: Vec<&'static str>) -> Self {
+        Self {
+            weights: self.weights.clone(),
+            ..Self::from_words_vec(words)
         }
     }
 
@@ -112,6 +197,17 @@ impl Dict {
         self.words_set.contains(word)
     }
 
+    #[inline]
+    pub fn is_weighted(&self) -> bool {
+        self.weights.is_some()
+    }
+
+    pub fn weight(&self, word: &str) -> f64 {
+        self.weights.as_ref().map_or(1.0, |weights| {
+            weights.get(word).copied().unwrap_or_default()
+        })
+    }
+
     pub fn deny_chars_at_poses(&mut self, poses: &HashSet<CharPos>, chars: &HashSet<char>) {
         self.deny_chars_helper(self.word_index_by_poses_and_chars(poses, chars));
     }
@@ -124,6 +220,10 @@ impl Dict {
         self.deny_chars_helper(self.word_index_by_char(chars));
     }
 
+    pub fn retain(&mut self, mut f: impl FnMut(&'static str) -> bool) {
+        *self = self.with_words(self.words.iter().copied().filter(|&word| f(word)).collect());
+    }
+
     fn deny_chars_helper(&mut self, word_indices_to_remove: HashSet<WordIndex>) {
         if !word_indices_to_remove.is_empty() {
             *self = self.remove_indices(word_indices_to_remove);
@@ -136,7 +236,7 @@ impl Dict {
 
     fn only_chars_helper(&mut self, word_indices_to_save: HashSet<WordIndex>) {
         *self = if word_indices_to_save.is_empty() {
-            Dict::empty()
+            self.with_words(vec![])
         } else {
             self.save_indices(word_indices_to_save)
         }
@@ -168,23 +268,27 @@ impl Dict {
     }
 
     fn remove_indices(&self, word_indices_to_remove: HashSet<WordIndex>) -> Self {
-        self.words
-            .iter()
-            .enumerate()
-            .map(|(index, &s)| (WordIndex(index), s))
-            .filter(|(word_index, _)| !word_indices_to_remove.contains(word_index))
-            .map(|(_, s)| s)
-            .collect()
+        self.with_words(
+            self.words
+                .iter()
+                .enumerate()
+                .map(|(index, &s)| (WordIndex(index), s))
+                .filter(|(word_index, _)| !word_indices_to_remove.contains(word_index))
+                .map(|(_, s)| s)
+                .collect(),
+        )
     }
 
     fn save_indices(&self, word_indices_to_save: HashSet<WordIndex>) -> Self {
-        self.words
-            .iter()
-            .enumerate()
-            .map(|(index, &s)| (WordIndex(index), s))
-            .filter(|(word_index, _)| word_indices_to_save.contains(word_index))
-            .map(|(_, s)| s)
-            .collect()
+        self.with_words(
+            self.words
+                .iter()
+                .enumerate()
+                .map(|(index, &s)| (WordIndex(index), s))
+                .filter(|(word_index, _)| word_indices_to_save.contains(word_index))
+                .map(|(_, s)| s)
+                .collect(),
+        )
     }
 }
 
@@ -326,6 +430,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn retain() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("азан"));
+        assert_eq!(dict.words(), ["казан", "сазан", "фазан"]);
+    }
+
     #[test]
     fn deny_chars_at_pos_empty() {
         let mut dict = Dict::default();
@@ -418,4 +529,86 @@ mod tests {
         dict.only_chars(&chars);
         assert_eq!(dict, Dict::empty());
     }
+
+    #[test]
+    fn parse() {
+        let dict = Dict::parse("сазан\nказан\n\nфазан\n").unwrap();
+        assert_eq!(dict.words(), ["казан", "сазан", "фазан"]);
+        assert!(!dict.is_weighted());
+        assert_eq!(dict.weight("сазан"), 1.0);
+    }
+
+    #[test]
+    fn parse_lowercase() {
+        let dict = Dict::parse("Сазан 2\nКАЗАН\nсазан 3\n").unwrap();
+        assert_eq!(dict.words(), ["казан", "сазан"]);
+        assert_eq!(dict.weight("сазан"), 2.0);
+    }
+
+    #[test]
+    fn parse_weights() {
+        let dict = Dict::parse("сазан 12\nказан\t3.5\nфазан\n").unwrap();
+        assert_eq!(dict.words(), ["казан", "сазан", "фазан"]);
+        assert!(dict.is_weighted());
+        assert_eq!(dict.weight("сазан"), 12.0);
+        assert_eq!(dict.weight("казан"), 3.5);
+        assert_eq!(dict.weight("фазан"), 0.0);
+        assert_eq!(dict.weight("шалаш"), 0.0);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Dict::parse("сазан 12\nказан 3 4\n"),
+            Err(DictError::UnexpectedColumns(2))
+        );
+        assert_eq!(
+            Dict::parse("сазан много\n"),
+            Err(DictError::InvalidWeight(1, "много".to_string()))
+        );
+        assert_eq!(
+            Dict::parse("сазан -1\n"),
+            Err(DictError::InvalidWeight(1, "-1".to_string()))
+        );
+        assert_eq!(
+            Dict::parse("казан 1\nсазан NaN\n"),
+            Err(DictError::InvalidWeight(2, "NaN".to_string()))
+        );
+        assert_eq!(
+            Dict::parse("сазан inf\n"),
+            Err(DictError::InvalidWeight(1, "inf".to_string()))
+        );
+        assert_eq!(
+            Dict::parse("казан\nсазан\nфазан 5\nабвгдеёжзий\n"),
+            Err(DictError::InvalidWordLength(4, "абвгдеёжзий".to_string()))
+        );
+        assert_eq!(
+            Dict::parse("казан\nказна\nказино\n"),
+            Err(DictError::InvalidWordLength(3, "казино".to_string()))
+        );
+        assert_eq!(
+            Dict::parse("абвгдеёжзий\n"),
+            Err(DictError::InvalidWordLength(1, "абвгдеёжзий".to_string()))
+        );
+    }
+
+    #[test]
+    fn load_missing_file() {
+        assert_eq!(
+            Dict::load("/nonexistent/mordle-dict.txt").map(|_| ()),
+            Err(DictError::IoError(io::ErrorKind::NotFound.into()))
+        );
+    }
+
+    #[test]
+    fn weights_kept_by_filters() {
+        let mut dict = Dict::default().with_weights([("сазан", 2.0), ("казан", 1.0)]);
+        dict.retain(|word| word.ends_with("азан"));
+        dict.deny_chars(&['к'].into());
+        assert_eq!(dict.words(), ["сазан", "фазан"]);
+        assert_eq!(dict.weight("сазан"), 2.0);
+        assert_eq!(dict.weight("фазан"), 0.0);
+        dict.only_chars_at_poses(&[CharPos(0)].into(), &['с'].into());
+        assert_eq!(dict.weight("сазан"), 2.0);
+    }
 }
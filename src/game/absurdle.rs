@@ -0,0 +1,180 @@
+use crate::{attempt::AttemptError, solver, Attempt, Dict, GameError, GameFinishStatus};
+use std::io::{BufRead, Write};
+
+// the host commits to no word and answers every guess with the largest bucket of the words left,
+// so it concedes only when the guess is the last of them
+pub struct Absurdle<'a> {
+    dict: &'a Dict,
+    candidates: Dict,
+    max_tries: usize,
+    tries: Vec<Attempt>,
+}
+
+impl<'a> Absurdle<'a> {
+    pub fn new(dict: &'a Dict, max_tries: usize) -> Self {
+        Self {
+            dict,
+            candidates: dict.clone(),
+            max_tries,
+            tries: vec![],
+        }
+    }
+
+    pub fn try_input(&mut self, input: &str) -> Result<&Attempt, GameError> {
+        match self.finish_status() {
+            None => {
+                let word_len = self
+                    .dict
+                    .words()
+                    .first()
+                    .map_or(0, |word| word.chars().count());
+                if input.chars().count() != word_len {
+                    return Err(AttemptError::InputLengthMismatch.into());
+                }
+                if !self.dict.word_in_dict(input) {
+                    return Err(AttemptError::WordNotInDict.into());
+                }
+                let bucket = solver::partition(input, &self.candidates)
+                    .into_iter()
+                    .next()
+                    .unwrap_or_else(|| unreachable!());
+                self.candidates.retain(|word| bucket.words.contains(&word));
+                self.tries.push(bucket.attempt);
+                Ok(self.tries.last().unwrap_or_else(|| unreachable!()))
+            }
+            Some(GameFinishStatus::Win) => Err(GameError::AlreadyWin),
+            Some(GameFinishStatus::Fail) => Err(GameError::TriesExhausted),
+        }
+    }
+
+    #[inline]
+    pub fn finish_status(&self) -> Option<GameFinishStatus> {
+        if self.tries.len() > self.max_tries {
+            Some(GameFinishStatus::Fail)
+        } else if let Some(true) = self.tries.last().map(|attempt| attempt.is_win_attempt()) {
+            Some(GameFinishStatus::Win)
+        } else if self.tries.len() == self.max_tries {
+            Some(GameFinishStatus::Fail)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn max_tries(&self) -> usize {
+        self.max_tries
+    }
+
+    #[inline]
+    pub fn tries(&self) -> &[Attempt] {
+        &self.tries
+    }
+
+    #[inline]
+    pub fn candidates(&self) -> &Dict {
+        &self.candidates
+    }
+
+    pub fn main_loop(
+        &mut self,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<GameFinishStatus, GameError> {
+        let mut lines = r.lines();
+        for t in 1usize.. {
+            loop {
+                write!(w, "Enter try {t} of {}: ", self.max_tries())?;
+                w.flush()?;
+
+                match self.try_input(
+                    lines
+                        .next()
+                        .ok_or(GameError::UnexpectedEndOfFile)??
+                        .to_lowercase()
+                        .as_str(),
+                ) {
+                    Ok(attempt) => {
+                        writeln!(w, "{attempt}")?;
+                        break;
+                    }
+                    Err(err @ GameError::AttemptError(_)) => {
+                        writeln!(w, "{err}")?;
+                    }
+                    other => {
+                        other?;
+                    }
+                }
+            }
+            if let Some(status) = self.finish_status() {
+                return Ok(status);
+            }
+            writeln!(w, "Words left: {}", self.candidates.words().len())?;
+        }
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn try_input() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let mut game = Absurdle::new(&dict, 6);
+        assert_eq!(
+            game.try_input("казан").map(|attempt| attempt.to_string()),
+            Ok("к а+з+а+н+".to_string())
+        );
+        assert_eq!(game.candidates().words(), ["сазан", "фазан"]);
+        // a tie goes to the bucket revealing less
+        assert_eq!(
+            game.try_input("сазан").map(|attempt| attempt.to_string()),
+            Ok("с а+з+а+н+".to_string())
+        );
+        assert_eq!(game.finish_status(), None);
+        assert_eq!(
+            game.try_input("фазан").map(|attempt| attempt.to_string()),
+            Ok("ф+а+з+а+н+".to_string())
+        );
+        assert_eq!(game.finish_status(), Some(GameFinishStatus::Win));
+        assert_eq!(game.try_input("фазан"), Err(GameError::AlreadyWin));
+    }
+
+    #[test]
+    fn try_input_errors() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let mut game = Absurdle::new(&dict, 6);
+        assert_eq!(
+            game.try_input("каз"),
+            Err(AttemptError::InputLengthMismatch.into())
+        );
+        assert_eq!(
+            game.try_input("абвгд"),
+            Err(AttemptError::WordNotInDict.into())
+        );
+        assert!(game.tries().is_empty());
+    }
+
+    #[test]
+    fn main_loop_fail() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let mut game = Absurdle::new(&dict, 2);
+        let mut out = vec![];
+        let mut inp = Cursor::new("казан\nабвгд\nказан\n");
+        assert_eq!(
+            game.main_loop(&mut inp, &mut out),
+            Ok(GameFinishStatus::Fail)
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "\
+            Enter try 1 of 2: к а+з+а+н+\n\
+            Words left: 1\n\
+            Enter try 2 of 2: Attempt error: Word not in dictionary\n\
+            Enter try 2 of 2: к а+з+а+н+\n\
+            "
+        );
+    }
+}
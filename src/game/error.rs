@@ -11,6 +11,7 @@ pub enum GameError {
     AlreadyWin,
     AttemptError(AttemptError),
     GameWordNotInDict,
+    GameWordsNotDisjoint,
     IoError(io::Error),
     UnexpectedEndOfFile,
 }
@@ -23,6 +24,7 @@ impl PartialEq for GameError {
             Self::AlreadyWin => matches!(other, Self::AlreadyWin),
             Self::AttemptError(e) => matches!(other, Self::AttemptError(oe) if e == oe),
             Self::GameWordNotInDict => matches!(other, Self::GameWordNotInDict),
+            Self::GameWordsNotDisjoint => matches!(other, Self::GameWordsNotDisjoint),
             Self::IoError(io) => {
                 matches!(other, Self::IoError(other_io) if io.kind() == other_io.kind())
             }
@@ -54,6 +56,7 @@ impl Display for GameError {
             Self::AlreadyWin => write!(f, "Already win"),
             Self::AttemptError(attempt_error) => write!(f, "Attempt error: {attempt_error}"),
             Self::GameWordNotInDict => write!(f, "Game initiated with word not in dict"),
+            Self::GameWordsNotDisjoint => write!(f, "Game initiated with words sharing letters"),
             Self::IoError(err) => write!(f, "I/O Error: {err}"),
             Self::UnexpectedEndOfFile => write!(f, "Unexpected end of file"),
         }
@@ -0,0 +1,90 @@
+use crate::{attempt::AttemptError, game::Feedback, CharPositions, Dict, Game, JottoAttempt};
+
+// count-only feedback gives no letter away, so no keyboard is shown
+pub type JottoGame<'a> = Game<'a, JottoAttempt>;
+
+impl Feedback for JottoAttempt {
+    const KEYBOARD: bool = false;
+
+    #[inline]
+    fn inspect_input(
+        input: &str,
+        char_positions: &CharPositions,
+        dict: &Dict,
+    ) -> Result<Self, AttemptError> {
+        Self::inspect_input(input, char_positions, dict)
+    }
+
+    #[inline]
+    fn is_win_attempt(&self) -> bool {
+        self.is_win_attempt()
+    }
+
+    #[inline]
+    fn absent_chars(&self) -> Vec<char> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameError, GameFinishStatus};
+    use std::io::Cursor;
+
+    #[test]
+    fn new_not_in_dict() {
+        let dict = Dict::default();
+        assert_eq!(
+            JottoGame::with_word(&dict, "абвгд", 5).map(|_| ()),
+            Err(GameError::GameWordNotInDict)
+        );
+    }
+
+    #[test]
+    fn try_input_not_in_dict() {
+        let dict = Dict::default();
+        let mut game = JottoGame::with_word(&dict, "сазан", 5).unwrap();
+        assert_eq!(
+            game.try_input("абвгд"),
+            Err(AttemptError::WordNotInDict.into())
+        );
+        assert_eq!(game.finish_status(), None);
+        assert!(game.tries().is_empty());
+    }
+
+    #[test]
+    fn main_loop_win() {
+        let dict = Dict::default();
+        let mut game = JottoGame::with_word(&dict, "сазан", 2).unwrap();
+        let mut out = vec![];
+        let mut inp = Cursor::new("казна\nсазан\n");
+        assert_eq!(
+            game.main_loop(&mut inp, &mut out),
+            Ok(GameFinishStatus::Win)
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "\
+            Enter try 1 of 2: казна 2+2?\n\
+            Enter try 2 of 2: сазан 5+0?\n\
+            "
+        );
+    }
+
+    #[test]
+    fn main_loop_fail() {
+        let dict = Dict::default();
+        let mut game = JottoGame::with_word(&dict, "сазан", 1).unwrap();
+        let mut out = vec![];
+        let mut inp = Cursor::new("казан\n");
+        assert_eq!(
+            game.main_loop(&mut inp, &mut out),
+            Ok(GameFinishStatus::Fail)
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "Enter try 1 of 1: казан 4+0?\n"
+        );
+    }
+}
@@ -1,24 +1,81 @@
+mod absurdle;
 mod error;
+mod jotto;
 mod status;
+mod survival;
+mod xordle;
 
+pub use absurdle::Absurdle;
 pub use error::GameError;
+pub use jotto::JottoGame;
 pub use status::GameFinishStatus;
+pub use survival::Survival;
+pub use xordle::Xordle;
 
-use crate::{Attempt, CharPositions, CharResult, Dict};
+use crate::{attempt::AttemptError, Attempt, CharPositions, CharResult, Dict};
 use std::{
     collections::HashSet,
+    fmt::Display,
     io::{BufRead, Write},
 };
 
-pub struct Game<'a> {
+// what the game tells about a guess
+pub trait Feedback: Display + Sized {
+    // whether the letters not ruled out yet are shown before every try
+    const KEYBOARD: bool;
+
+    fn inspect_input(
+        input: &str,
+        char_positions: &CharPositions,
+        dict: &Dict,
+    ) -> Result<Self, AttemptError>;
+    fn is_win_attempt(&self) -> bool;
+    fn absent_chars(&self) -> Vec<char>;
+}
+
+impl Feedback for Attempt {
+    const KEYBOARD: bool = true;
+
+    #[inline]
+    fn inspect_input(
+        input: &str,
+        char_positions: &CharPositions,
+        dict: &Dict,
+    ) -> Result<Self, AttemptError> {
+        Self::inspect_input(input, char_positions, dict)
+    }
+
+    #[inline]
+    fn is_win_attempt(&self) -> bool {
+        self.is_win_attempt()
+    }
+
+    fn absent_chars(&self) -> Vec<char> {
+        let Self(attempt_chars) = self;
+        attempt_chars
+            .iter()
+            .filter(|attempt_char| attempt_char.state == CharResult::Unsuccessful)
+            .map(|attempt_char| attempt_char.ch)
+            .collect()
+    }
+}
+
+pub struct Game<'a, F = Attempt> {
     dict: &'a Dict,
     word_index: CharPositions,
     max_tries: usize,
-    tries: Vec<Attempt>,
+    tries: Vec<F>,
 }
 
 impl<'a> Game<'a> {
+    #[inline]
     pub fn new(dict: &'a Dict, word: &str, max_tries: usize) -> Result<Self, GameError> {
+        Self::with_word(dict, word, max_tries)
+    }
+}
+
+impl<'a, F: Feedback> Game<'a, F> {
+    pub fn with_word(dict: &'a Dict, word: &str, max_tries: usize) -> Result<Self, GameError> {
         if dict.word_in_dict(word) {
             Ok(Self {
                 dict,
@@ -31,10 +88,10 @@ impl<'a> Game<'a> {
         }
     }
 
-    pub fn try_input(&mut self, input: &str) -> Result<&Attempt, GameError> {
+    pub fn try_input(&mut self, input: &str) -> Result<&F, GameError> {
         match self.finish_status() {
             None => {
-                let attempt = Attempt::inspect_input(input, &self.word_index, self.dict)?;
+                let attempt = F::inspect_input(input, &self.word_index, self.dict)?;
                 self.tries.push(attempt);
                 Ok(self.tries.last().unwrap_or_else(|| unreachable!()))
             }
@@ -61,6 +118,11 @@ impl<'a> Game<'a> {
         self.max_tries
     }
 
+    #[inline]
+    pub fn tries(&self) -> &[F] {
+        &self.tries
+    }
+
     pub fn main_loop(
         &mut self,
         r: &mut impl BufRead,
@@ -70,7 +132,9 @@ impl<'a> Game<'a> {
         let mut avail_chars: HashSet<_> = self.dict.global_char_index().keys().copied().collect();
         for t in 1usize.. {
             loop {
-                print_chars(w, &avail_chars)?;
+                if F::KEYBOARD {
+                    print_chars(w, &avail_chars)?;
+                }
 
                 write!(w, "Enter try {t} of {}: ", self.max_tries())?;
                 w.flush()?;
@@ -83,12 +147,7 @@ impl<'a> Game<'a> {
                         .as_str(),
                 ) {
                     Ok(attempt) => {
-                        let Attempt(attempt_chars) = attempt;
-                        for ch in attempt_chars
-                            .iter()
-                            .filter(|attempt_char| attempt_char.state == CharResult::Unsuccessful)
-                            .map(|attempt_char| attempt_char.ch)
-                        {
+                        for ch in attempt.absent_chars() {
                             avail_chars.remove(&ch);
                         }
                         writeln!(w, "{attempt}")?;
@@ -222,6 +281,22 @@ mod tests {
         assert!(game.tries.last().unwrap().is_win_attempt());
     }
 
+    // a letter is misplaced only as many times as the word has it on other positions,
+    // before it was reported misplaced on every occurrence: "к а?с к а?"
+    #[test]
+    fn try_input_repeated_misplaced() {
+        let dict = Dict::default();
+        let mut game = Game::new(&dict, "аборт", 6).unwrap();
+        assert_eq!(
+            game.try_input("каска").map(ToString::to_string),
+            Ok("к а?с к а ".to_string())
+        );
+        assert_eq!(
+            game.try_input("шалаш").map(ToString::to_string),
+            Ok("ш а?л а ш ".to_string())
+        );
+    }
+
     #[test]
     fn tries_exhausted() {
         let dict = Dict::default();
@@ -0,0 +1,145 @@
+use crate::{Dict, Game, GameError, GameFinishStatus};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+};
+
+pub struct Survival<'a> {
+    dict: &'a Dict,
+    tries_left: usize,
+    bonus_tries: usize,
+    used_words: HashSet<&'static str>,
+    score: usize,
+}
+
+impl<'a> Survival<'a> {
+    pub fn new(dict: &'a Dict, initial_tries: usize, bonus_tries: usize) -> Self {
+        Self {
+            dict,
+            tries_left: initial_tries,
+            bonus_tries,
+            used_words: HashSet::new(),
+            score: 0,
+        }
+    }
+
+    #[inline]
+    pub fn tries_left(&self) -> usize {
+        self.tries_left
+    }
+
+    #[inline]
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    pub fn next_word(&mut self, rng: &mut impl Rng) -> Option<&'static str> {
+        let word = *self
+            .dict
+            .words()
+            .iter()
+            .filter(|word| !self.used_words.contains(*word))
+            .collect_vec()
+            .choose(rng)?;
+        self.used_words.insert(word);
+        Some(word)
+    }
+
+    pub fn finish_word(&mut self, game: &Game) -> Option<GameFinishStatus> {
+        let status = game.finish_status()?;
+        self.tries_left = self.tries_left.saturating_sub(game.tries().len());
+        if status == GameFinishStatus::Win {
+            self.score += 1;
+            self.tries_left += self.bonus_tries;
+        }
+        Some(status)
+    }
+
+    pub fn main_loop(
+        &mut self,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+        rng: &mut impl Rng,
+    ) -> Result<usize, GameError> {
+        while self.tries_left > 0 {
+            let Some(word) = self.next_word(rng) else {
+                break;
+            };
+            writeln!(
+                w,
+                "Word {}, tries left: {}",
+                self.score + 1,
+                self.tries_left
+            )?;
+            let mut game = Game::new(self.dict, word, self.tries_left)?;
+            game.main_loop(r, w)?;
+            match self.finish_word(&game) {
+                Some(GameFinishStatus::Win) => writeln!(w, "Solved!")?,
+                Some(GameFinishStatus::Fail) => writeln!(w, "Word is: {word}")?,
+                None => unreachable!(),
+            }
+        }
+        Ok(self.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::io::Cursor;
+
+    #[test]
+    fn next_word_no_repeats() {
+        let dict = Dict::from_iter(["казан", "сазан", "фазан"]);
+        let mut survival = Survival::new(&dict, 6, 1);
+        let mut rng = StdRng::seed_from_u64(0);
+        let words: HashSet<_> = (0..3)
+            .map(|_| survival.next_word(&mut rng).unwrap())
+            .collect();
+        assert_eq!(words, dict.words_set().clone());
+        assert_eq!(survival.next_word(&mut rng), None);
+    }
+
+    #[test]
+    fn finish_word_carries_tries() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let mut survival = Survival::new(&dict, 6, 2);
+        let mut game = Game::new(&dict, "сазан", survival.tries_left()).unwrap();
+        assert_eq!(survival.finish_word(&game), None);
+        game.try_input("казан").unwrap();
+        game.try_input("сазан").unwrap();
+        assert_eq!(survival.finish_word(&game), Some(GameFinishStatus::Win));
+        assert_eq!(survival.tries_left(), 6);
+        assert_eq!(survival.score(), 1);
+    }
+
+    #[test]
+    fn finish_word_fail_exhausts_pool() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let mut survival = Survival::new(&dict, 1, 2);
+        let mut game = Game::new(&dict, "сазан", survival.tries_left()).unwrap();
+        game.try_input("казан").unwrap();
+        assert_eq!(survival.finish_word(&game), Some(GameFinishStatus::Fail));
+        assert_eq!(survival.tries_left(), 0);
+        assert_eq!(survival.score(), 0);
+    }
+
+    #[test]
+    fn main_loop_dict_exhausted() {
+        let dict = Dict::from_iter(["сазан"]);
+        let mut survival = Survival::new(&dict, 6, 1);
+        let mut out = vec![];
+        let mut inp = Cursor::new("сазан\n");
+        assert_eq!(
+            survival.main_loop(&mut inp, &mut out, &mut StdRng::seed_from_u64(0)),
+            Ok(1)
+        );
+        assert_eq!(survival.tries_left(), 6);
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .starts_with("Word 1, tries left: 6\n"));
+    }
+}
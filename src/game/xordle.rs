@@ -0,0 +1,245 @@
+use crate::{
+    attempt::AttemptChar, dict::WordIndex, Attempt, CharPositions, CharResult, Dict, GameError,
+    GameFinishStatus,
+};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+    iter::zip,
+};
+
+pub struct Xordle<'a> {
+    dict: &'a Dict,
+    words: [&'static str; 2],
+    word_indices: [CharPositions; 2],
+    solved: [bool; 2],
+    max_tries: usize,
+    tries: Vec<Attempt>,
+}
+
+impl<'a> Xordle<'a> {
+    pub fn new(
+        dict: &'a Dict,
+        words: [&'static str; 2],
+        max_tries: usize,
+    ) -> Result<Self, GameError> {
+        if !words.iter().all(|word| dict.word_in_dict(word)) {
+            Err(GameError::GameWordNotInDict)
+        } else if words[0].chars().any(|ch| words[1].contains(ch)) {
+            Err(GameError::GameWordsNotDisjoint)
+        } else {
+            Ok(Self {
+                dict,
+                words,
+                word_indices: words.map(CharPositions::new),
+                solved: [false; 2],
+                max_tries,
+                tries: vec![],
+            })
+        }
+    }
+
+    pub fn choose_words(dict: &Dict, rng: &mut impl Rng) -> Option<[&'static str; 2]> {
+        let mut first_indices = (0..dict.words().len()).map(WordIndex).collect_vec();
+        first_indices.shuffle(rng);
+        first_indices.into_iter().find_map(|first_index| {
+            let WordIndex(first) = first_index;
+            let first = dict.words()[first];
+            let overlapping: HashSet<_> = first
+                .chars()
+                .filter_map(|ch| dict.global_char_index().get(&ch))
+                .flatten()
+                .copied()
+                .collect();
+            let second = *dict
+                .words()
+                .iter()
+                .enumerate()
+                .filter(|&(index, _)| !overlapping.contains(&WordIndex(index)))
+                .map(|(_, word)| word)
+                .collect_vec()
+                .choose(rng)?;
+            Some([first, second])
+        })
+    }
+
+    pub fn try_input(&mut self, input: &str) -> Result<&Attempt, GameError> {
+        match self.finish_status() {
+            None => {
+                let [first, second] = [
+                    Attempt::inspect_input(input, &self.word_indices[0], self.dict)?,
+                    Attempt::inspect_input(input, &self.word_indices[1], self.dict)?,
+                ];
+                for (solved, attempt) in zip(&mut self.solved, [&first, &second]) {
+                    *solved |= attempt.is_win_attempt();
+                }
+                let (Attempt(first), Attempt(second)) = (first, second);
+                self.tries.push(Attempt(
+                    zip(first, second)
+                        .map(|(first, second)| AttemptChar {
+                            ch: first.ch,
+                            state: combine(first.state, second.state),
+                        })
+                        .collect(),
+                ));
+                Ok(self.tries.last().unwrap_or_else(|| unreachable!()))
+            }
+            Some(GameFinishStatus::Win) => Err(GameError::AlreadyWin),
+            Some(GameFinishStatus::Fail) => Err(GameError::TriesExhausted),
+        }
+    }
+
+    #[inline]
+    pub fn finish_status(&self) -> Option<GameFinishStatus> {
+        if self.tries.len() > self.max_tries {
+            Some(GameFinishStatus::Fail)
+        } else if self.solved.iter().all(|&solved| solved) {
+            Some(GameFinishStatus::Win)
+        } else if self.tries.len() == self.max_tries {
+            Some(GameFinishStatus::Fail)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn max_tries(&self) -> usize {
+        self.max_tries
+    }
+
+    #[inline]
+    pub fn words(&self) -> [&'static str; 2] {
+        self.words
+    }
+
+    pub fn main_loop(
+        &mut self,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<GameFinishStatus, GameError> {
+        let mut lines = r.lines();
+        for t in 1usize.. {
+            loop {
+                write!(w, "Enter try {t} of {}: ", self.max_tries())?;
+                w.flush()?;
+
+                let solved = self.solved;
+                match self.try_input(
+                    lines
+                        .next()
+                        .ok_or(GameError::UnexpectedEndOfFile)??
+                        .to_lowercase()
+                        .as_str(),
+                ) {
+                    Ok(attempt) => {
+                        writeln!(w, "{attempt}")?;
+                        for (word, _) in zip(self.words, zip(solved, self.solved))
+                            .filter(|(_, (was_solved, solved))| !was_solved && *solved)
+                        {
+                            writeln!(w, "Found: {word}")?;
+                        }
+                        break;
+                    }
+                    Err(err @ GameError::AttemptError(_)) => {
+                        writeln!(w, "{err}")?;
+                    }
+                    other => {
+                        other?;
+                    }
+                }
+            }
+            if let Some(status) = self.finish_status() {
+                return Ok(status);
+            }
+        }
+        unreachable!()
+    }
+}
+
+fn combine(first: CharResult, second: CharResult) -> CharResult {
+    match (first, second) {
+        (CharResult::Exact, _) | (_, CharResult::Exact) => CharResult::Exact,
+        (CharResult::NotInPosition, _) | (_, CharResult::NotInPosition) => {
+            CharResult::NotInPosition
+        }
+        _ => CharResult::Unsuccessful,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::io::Cursor;
+
+    #[test]
+    fn new_not_disjoint() {
+        let dict = Dict::default();
+        assert_eq!(
+            Xordle::new(&dict, ["сазан", "казан"], 7).map(|_| ()),
+            Err(GameError::GameWordsNotDisjoint)
+        );
+    }
+
+    #[test]
+    fn new_not_in_dict() {
+        let dict = Dict::default();
+        assert_eq!(
+            Xordle::new(&dict, ["сазан", "абвгд"], 7).map(|_| ()),
+            Err(GameError::GameWordNotInDict)
+        );
+    }
+
+    #[test]
+    fn choose_words() {
+        let dict = Dict::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..10 {
+            let [first, second] = Xordle::choose_words(&dict, &mut rng).unwrap();
+            assert!(first.chars().all(|ch| !second.contains(ch)));
+        }
+    }
+
+    #[test]
+    fn choose_words_none() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        assert_eq!(
+            Xordle::choose_words(&dict, &mut StdRng::seed_from_u64(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn try_input_combined() {
+        let dict = Dict::default();
+        let mut game = Xordle::new(&dict, ["сазан", "тупик"], 7).unwrap();
+        assert_eq!(
+            game.try_input("спорт").map(|attempt| attempt.to_string()),
+            Ok("с+п?о р т?".to_string())
+        );
+        assert_eq!(game.finish_status(), None);
+    }
+
+    #[test]
+    fn main_loop_win() {
+        let dict = Dict::default();
+        let mut game = Xordle::new(&dict, ["сазан", "тупик"], 7).unwrap();
+        let mut out = vec![];
+        let mut inp = Cursor::new("сазан\nтупик\n");
+        assert_eq!(
+            game.main_loop(&mut inp, &mut out),
+            Ok(GameFinishStatus::Win)
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "\
+            Enter try 1 of 7: с+а+з+а+н+\n\
+            Found: сазан\n\
+            Enter try 2 of 7: т+у+п+и+к+\n\
+            Found: тупик\n\
+            "
+        );
+    }
+}
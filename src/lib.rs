@@ -1,12 +1,17 @@
+pub mod assistant;
 mod attempt;
 mod char_pos;
 mod char_positions;
+pub mod cli;
 pub mod dict;
 mod game;
+pub mod puzzle;
 pub mod solver;
 
-pub use attempt::{Attempt, CharResult};
+pub use attempt::{Attempt, CharResult, JottoAttempt, Pattern};
 pub use char_pos::CharPos;
 pub use char_positions::CharPositions;
 pub use dict::Dict;
-pub use game::{Game, GameError, GameFinishStatus};
+pub use game::{
+    Absurdle, Feedback, Game, GameError, GameFinishStatus, JottoGame, Survival, Xordle,
+};
@@ -1,10 +1,16 @@
-use mordle::{Dict, Game, GameFinishStatus};
+use clap::{Parser, ValueEnum};
+use mordle::{
+    puzzle::{Difficulty, ReversePuzzle, SolveInOnePuzzle},
+    solver::WordRatings,
+    Absurdle, Dict, Game, GameFinishStatus, JottoGame, Survival, Xordle,
+};
 use rand::seq::SliceRandom;
 use std::{
     error::Error,
     fmt::{Debug, Display, Formatter},
     io,
-    io::{BufReader, BufWriter, Write},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
 };
 
 #[derive(Debug)]
@@ -31,16 +37,235 @@ impl From<io::Error> for MainErrors {
 
 impl Error for MainErrors {}
 
+#[derive(Parser)]
+#[command(author, version, about = "Guess the russian word", long_about = None)]
+struct Cli {
+    /// Game mode
+    #[arg(short, long, value_enum, default_value_t = Mode::Classic)]
+    mode: Mode,
+    /// Tries per word (initial tries pool in survival mode)
+    #[arg(short, long, default_value_t = 6)]
+    tries: usize,
+    /// Tries added to the pool for every solved word in survival mode
+    #[arg(short, long, default_value_t = 1)]
+    bonus: usize,
+    /// Puzzle difficulty, also of the secret word in classic mode with ratings
+    #[arg(short, long, value_enum, default_value_t = DifficultyArg::Medium)]
+    difficulty: DifficultyArg,
+    /// Word ratings CSV made by the difficulty tool, to pick the classic secret word by difficulty
+    #[arg(long)]
+    ratings: Option<PathBuf>,
+    /// Rows in reverse puzzle
+    #[arg(short, long, default_value_t = 4)]
+    rows: usize,
+    /// Pre-played guesses in solve-in-one puzzle
+    #[arg(short, long, default_value_t = 2)]
+    guesses: usize,
+    /// Words left consistent with pre-played guesses in solve-in-one puzzle
+    #[arg(short, long, default_value_t = 1)]
+    candidates: usize,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Mode {
+    /// Guess a single word
+    Classic,
+    /// Guess words back to back from a shared pool of tries
+    Survival,
+    /// Guess a single word with only exact and misplaced letter counts
+    Jotto,
+    /// Guess two words sharing no letters with combined feedback
+    Xordle,
+    /// Guess against a host changing the word to dodge every guess
+    Absurdle,
+    /// Find words producing given patterns for a known secret
+    Reverse,
+    /// Find the word in one try after pre-played guesses
+    SolveInOne,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum DifficultyArg {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl From<DifficultyArg> for Difficulty {
+    fn from(value: DifficultyArg) -> Self {
+        match value {
+            DifficultyArg::Easy => Self::Easy,
+            DifficultyArg::Medium => Self::Medium,
+            DifficultyArg::Hard => Self::Hard,
+        }
+    }
+}
+
 fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
     let dict = Dict::default();
+    let mut stdout = BufWriter::new(io::stdout().lock());
+    let mut stdin = BufReader::new(io::stdin().lock());
+    match cli.mode {
+        Mode::Classic => classic(
+            &dict,
+            cli.tries,
+            cli.ratings.as_deref(),
+            cli.difficulty.into(),
+            &mut stdin,
+            &mut stdout,
+        ),
+        Mode::Survival => survival(&dict, cli.tries, cli.bonus, &mut stdin, &mut stdout),
+        Mode::Jotto => jotto(&dict, cli.tries, &mut stdin, &mut stdout),
+        Mode::Xordle => xordle(&dict, cli.tries, &mut stdin, &mut stdout),
+        Mode::Absurdle => absurdle(&dict, cli.tries, &mut stdin, &mut stdout),
+        Mode::Reverse => reverse(
+            &dict,
+            cli.rows,
+            cli.difficulty.into(),
+            &mut stdin,
+            &mut stdout,
+        ),
+        Mode::SolveInOne => {
+            solve_in_one(&dict, cli.guesses, cli.candidates, &mut stdin, &mut stdout)
+        }
+    }
+}
+
+fn classic(
+    dict: &Dict,
+    tries: usize,
+    ratings: Option<&Path>,
+    difficulty: Difficulty,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
+    let words = match ratings {
+        Some(path) => WordRatings::load(path)?
+            .words(difficulty)
+            .into_iter()
+            .filter(|word| dict.word_in_dict(word))
+            .collect(),
+        None => dict.words().to_vec(),
+    };
+    let word = words
+        .choose(&mut rand::thread_rng())
+        .ok_or(MainErrors::EmptyDict)?;
+    let mut game = Game::new(dict, word, tries)?;
+    match game.main_loop(stdin, stdout)? {
+        GameFinishStatus::Win => {
+            writeln!(stdout, "Win!")?;
+        }
+        GameFinishStatus::Fail => {
+            writeln!(stdout, "Fail!")?;
+            writeln!(stdout, "Word is: {word}")?;
+        }
+    }
+    Ok(())
+}
+
+fn survival(
+    dict: &Dict,
+    tries: usize,
+    bonus: usize,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
+    let score =
+        Survival::new(dict, tries, bonus).main_loop(stdin, stdout, &mut rand::thread_rng())?;
+    writeln!(stdout, "Score: {score}")?;
+    Ok(())
+}
+
+fn jotto(
+    dict: &Dict,
+    tries: usize,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
     let word = dict
         .words()
         .choose(&mut rand::thread_rng())
         .ok_or(MainErrors::EmptyDict)?;
-    let mut game = Game::new(&dict, word, 6)?;
-    let mut stdout = BufWriter::new(io::stdout().lock());
-    let mut stdin = BufReader::new(io::stdin().lock());
-    match game.main_loop(&mut stdin, &mut stdout)? {
+    let mut game = JottoGame::with_word(dict, word, tries)?;
+    match game.main_loop(stdin, stdout)? {
+        GameFinishStatus::Win => {
+            writeln!(stdout, "Win!")?;
+        }
+        GameFinishStatus::Fail => {
+            writeln!(stdout, "Fail!")?;
+            writeln!(stdout, "Word is: {word}")?;
+        }
+    }
+    Ok(())
+}
+
+fn xordle(
+    dict: &Dict,
+    tries: usize,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
+    let words = Xordle::choose_words(dict, &mut rand::thread_rng()).ok_or(MainErrors::EmptyDict)?;
+    let mut game = Xordle::new(dict, words, tries)?;
+    match game.main_loop(stdin, stdout)? {
+        GameFinishStatus::Win => {
+            writeln!(stdout, "Win!")?;
+        }
+        GameFinishStatus::Fail => {
+            writeln!(stdout, "Fail!")?;
+            let [first, second] = words;
+            writeln!(stdout, "Words are: {first}, {second}")?;
+        }
+    }
+    Ok(())
+}
+
+fn absurdle(
+    dict: &Dict,
+    tries: usize,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut game = Absurdle::new(dict, tries);
+    match game.main_loop(stdin, stdout)? {
+        GameFinishStatus::Win => {
+            writeln!(stdout, "Win!")?;
+        }
+        GameFinishStatus::Fail => {
+            writeln!(stdout, "Fail!")?;
+            writeln!(stdout, "Words left: {}", game.candidates().words().len())?;
+        }
+    }
+    Ok(())
+}
+
+fn reverse(
+    dict: &Dict,
+    rows: usize,
+    difficulty: Difficulty,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut rng = rand::thread_rng();
+    let word = dict.words().choose(&mut rng).ok_or(MainErrors::EmptyDict)?;
+    let puzzle = ReversePuzzle::generate(dict, word, rows, difficulty, &mut rng)?;
+    let mistakes = puzzle.main_loop(dict, stdin, stdout)?;
+    writeln!(stdout, "Solved with {mistakes} mistakes!")?;
+    Ok(())
+}
+
+fn solve_in_one(
+    dict: &Dict,
+    guesses: usize,
+    candidates: usize,
+    stdin: &mut impl BufRead,
+    stdout: &mut impl Write,
+) -> anyhow::Result<()> {
+    let mut rng = rand::thread_rng();
+    let word = dict.words().choose(&mut rng).ok_or(MainErrors::EmptyDict)?;
+    let puzzle = SolveInOnePuzzle::generate(dict, word, guesses, candidates, &mut rng)?;
+    match puzzle.main_loop(dict, stdin, stdout)? {
         GameFinishStatus::Win => {
             writeln!(stdout, "Win!")?;
         }
@@ -0,0 +1,71 @@
+use crate::attempt::AttemptError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+};
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    SecretNotInDict,
+    NotEnoughPatterns,
+    GenerationFailed,
+    EmptyGrid,
+    GridRowLengthMismatch(usize),
+    AttemptError(AttemptError),
+    IoError(io::Error),
+    UnexpectedEndOfFile,
+}
+
+#[cfg(test)]
+impl PartialEq for PuzzleError {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::SecretNotInDict => matches!(other, Self::SecretNotInDict),
+            Self::NotEnoughPatterns => matches!(other, Self::NotEnoughPatterns),
+            Self::GenerationFailed => matches!(other, Self::GenerationFailed),
+            Self::EmptyGrid => matches!(other, Self::EmptyGrid),
+            Self::GridRowLengthMismatch(row) => {
+                matches!(other, Self::GridRowLengthMismatch(other_row) if row == other_row)
+            }
+            Self::AttemptError(e) => matches!(other, Self::AttemptError(oe) if e == oe),
+            Self::IoError(io) => {
+                matches!(other, Self::IoError(other_io) if io.kind() == other_io.kind())
+            }
+            Self::UnexpectedEndOfFile => matches!(other, Self::UnexpectedEndOfFile),
+        }
+    }
+}
+
+impl Error for PuzzleError {}
+
+impl From<AttemptError> for PuzzleError {
+    #[inline]
+    fn from(value: AttemptError) -> Self {
+        Self::AttemptError(value)
+    }
+}
+
+impl From<io::Error> for PuzzleError {
+    #[inline]
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SecretNotInDict => write!(f, "Puzzle secret word not in dict"),
+            Self::NotEnoughPatterns => write!(f, "Not enough patterns for puzzle"),
+            Self::GenerationFailed => write!(f, "Can not generate puzzle"),
+            Self::EmptyGrid => write!(f, "No rows of colored tiles in grid"),
+            Self::GridRowLengthMismatch(row) => {
+                write!(f, "Grid row {row} length not matched to word")
+            }
+            Self::AttemptError(attempt_error) => write!(f, "Attempt error: {attempt_error}"),
+            Self::IoError(err) => write!(f, "I/O Error: {err}"),
+            Self::UnexpectedEndOfFile => write!(f, "Unexpected end of file"),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    attempt::AttemptError,
+    puzzle::{patterns_for_secret, PuzzleError},
+    solver::PatternMatrix,
+    Dict, Pattern,
+};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+// lines without a single tile, like the "Wordle 123 3/6" header, are skipped
+pub fn parse_grid(grid: &str, word_len: usize) -> Result<Vec<Pattern>, PuzzleError> {
+    let mut rows = vec![];
+    for line in grid.lines().filter(|line| {
+        line.chars()
+            .any(|ch| matches!(ch, '🟩' | '🟧' | '🟨' | '🟦' | '⬜' | '⬛'))
+    }) {
+        let pattern = Pattern::from_emoji(line)
+            .map_err(|err| PuzzleError::AttemptError(AttemptError::ParseError(err)))?;
+        let len = line
+            .chars()
+            .filter(|&ch| !ch.is_whitespace() && ch != '\u{fe0f}')
+            .count();
+        if len != word_len {
+            return Err(PuzzleError::GridRowLengthMismatch(rows.len() + 1));
+        }
+        rows.push(pattern);
+    }
+    if rows.is_empty() {
+        Err(PuzzleError::EmptyGrid)
+    } else {
+        Ok(rows)
+    }
+}
+
+fn patterns_by_code(dict: &Dict, answer: &str) -> HashMap<Pattern, Vec<&'static str>> {
+    patterns_for_secret(dict, answer)
+        .into_iter()
+        .map(|(states, words)| (Pattern::from_states(states.into_iter()), words))
+        .collect()
+}
+
+// the guesses of the dictionary producing every row for the answer, an empty list means the grid
+// can't come from this answer
+pub fn grid_guesses(dict: &Dict, answer: &str, rows: &[Pattern]) -> Vec<Vec<&'static str>> {
+    let patterns = patterns_by_code(dict, answer);
+    rows.iter()
+        .map(|pattern| patterns.get(pattern).cloned().unwrap_or_default())
+        .collect()
+}
+
+// answers of the matrix for which some guess produces every row
+pub fn grid_answers(matrix: &PatternMatrix, rows: &[Pattern]) -> Vec<&'static str> {
+    let rows = rows.iter().copied().unique().collect_vec();
+    let guesses = matrix.guesses().len();
+    matrix
+        .answers()
+        .iter()
+        .enumerate()
+        .filter(|&(answer, _)| {
+            rows.iter()
+                .all(|&row| (0..guesses).any(|guess| matrix.get(guess, answer) == row))
+        })
+        .map(|(_, &answer)| answer)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attempt::ParseAttemptError;
+
+    fn dict() -> Dict {
+        Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"])
+    }
+
+    #[test]
+    fn parse_grid() {
+        assert_eq!(
+            super::parse_grid("Мордле 42 3/6\n\n⬜🟩🟩🟩🟩\n🟩🟩🟩🟩🟩\n", 5),
+            Ok(vec![
+                Pattern::from_emoji("⬜🟩🟩🟩🟩").unwrap(),
+                Pattern::win(5)
+            ])
+        );
+        assert_eq!(
+            super::parse_grid("⬜🟩🟩🟩🟩\n🟩🟩🟩🟩", 5),
+            Err(PuzzleError::GridRowLengthMismatch(2))
+        );
+        assert_eq!(
+            super::parse_grid("⬜🟩x🟩🟩", 5),
+            Err(PuzzleError::AttemptError(AttemptError::ParseError(
+                ParseAttemptError::CharResultUnexpected('x')
+            )))
+        );
+        assert_eq!(
+            super::parse_grid("Мордле 42", 5),
+            Err(PuzzleError::EmptyGrid)
+        );
+    }
+
+    #[test]
+    fn grid_guesses() {
+        let dict = dict();
+        let rows = super::parse_grid("⬜🟩🟩🟩🟩\n🟩🟩🟩🟩🟩", 5).unwrap();
+        assert_eq!(
+            super::grid_guesses(&dict, "сазан", &rows),
+            [vec!["казан", "фазан"], vec!["сазан"]]
+        );
+        // no other word ends with аска
+        assert_eq!(
+            super::grid_guesses(&dict, "каска", &rows),
+            [vec![], vec!["каска"]]
+        );
+    }
+
+    #[test]
+    fn grid_answers() {
+        let dict = dict();
+        let matrix = PatternMatrix::from_dict(&dict);
+        let rows = super::parse_grid("⬜🟩🟩🟩🟩\n🟩🟩🟩🟩🟩", 5).unwrap();
+        assert_eq!(
+            super::grid_answers(&matrix, &rows),
+            ["казан", "сазан", "фазан"]
+        );
+        assert_eq!(
+            super::grid_answers(&matrix, &rows[1..]),
+            dict.words().to_vec()
+        );
+        // no guess matches only the first letter of any answer
+        let rows = super::parse_grid("🟩⬜⬜⬜⬜\n⬜🟩🟩🟩🟩", 5).unwrap();
+        assert_eq!(super::grid_answers(&matrix, &rows), Vec::<&str>::new());
+    }
+}
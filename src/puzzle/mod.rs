@@ -0,0 +1,16 @@
+mod error;
+mod grid;
+mod reverse;
+mod solve_in_one;
+
+pub use error::PuzzleError;
+pub use grid::{grid_answers, grid_guesses, parse_grid};
+pub use reverse::{patterns_for_secret, ReversePuzzle};
+pub use solve_in_one::SolveInOnePuzzle;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
@@ -0,0 +1,283 @@
+use crate::{
+    puzzle::{Difficulty, PuzzleError},
+    Attempt, CharPositions, CharResult, Dict,
+};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+pub struct ReversePuzzle {
+    secret: &'static str,
+    rows: Vec<Vec<CharResult>>,
+    solutions: Vec<Vec<&'static str>>,
+}
+
+pub fn patterns_for_secret(
+    dict: &Dict,
+    secret: &str,
+) -> HashMap<Vec<CharResult>, Vec<&'static str>> {
+    let char_positions = CharPositions::new(secret);
+    dict.words()
+        .iter()
+        .filter_map(|&word| {
+            Attempt::inspect_input(word, &char_positions, dict)
+                .ok()
+                .map(|attempt| (states(&attempt), word))
+        })
+        .into_group_map()
+}
+
+fn states(attempt: &Attempt) -> Vec<CharResult> {
+    let Attempt(attempt_chars) = attempt;
+    attempt_chars.iter().map(|ac| ac.state).collect()
+}
+
+impl ReversePuzzle {
+    pub fn generate(
+        dict: &Dict,
+        secret: &'static str,
+        rows: usize,
+        difficulty: Difficulty,
+        rng: &mut impl Rng,
+    ) -> Result<Self, PuzzleError> {
+        if !dict.word_in_dict(secret) {
+            return Err(PuzzleError::SecretNotInDict);
+        }
+        let mut patterns = patterns_for_secret(dict, secret)
+            .into_iter()
+            .filter(|(pattern, _)| !pattern.iter().all(|&state| state == CharResult::Exact))
+            .collect_vec();
+        patterns.sort_unstable_by(|(_, a_words), (_, b_words)| {
+            a_words
+                .len()
+                .cmp(&b_words.len())
+                .then_with(|| a_words.cmp(b_words))
+        });
+        let tier = match difficulty {
+            Difficulty::Hard => 0,
+            Difficulty::Medium => 1,
+            Difficulty::Easy => 2,
+        };
+        let tier = &patterns[patterns.len() * tier / 3..patterns.len() * (tier + 1) / 3];
+        if tier.len() < rows {
+            return Err(PuzzleError::NotEnoughPatterns);
+        }
+        let (rows, solutions) = tier
+            .choose_multiple(rng, rows)
+            .sorted_by_key(|(pattern, _)| {
+                (
+                    pattern
+                        .iter()
+                        .filter(|&&state| state == CharResult::Exact)
+                        .count(),
+                    pattern
+                        .iter()
+                        .filter(|&&state| state == CharResult::NotInPosition)
+                        .count(),
+                )
+            })
+            .cloned()
+            .unzip();
+        Ok(Self {
+            secret,
+            rows,
+            solutions,
+        })
+    }
+
+    #[inline]
+    pub fn secret(&self) -> &'static str {
+        self.secret
+    }
+
+    #[inline]
+    pub fn rows(&self) -> &[Vec<CharResult>] {
+        &self.rows
+    }
+
+    #[inline]
+    pub fn solutions(&self) -> &[Vec<&'static str>] {
+        &self.solutions
+    }
+
+    pub fn check(&self, row: usize, word: &str, dict: &Dict) -> Result<bool, PuzzleError> {
+        let attempt = Attempt::inspect_input(word, &self.secret.into(), dict)?;
+        Ok(self.rows.get(row) == Some(&states(&attempt)))
+    }
+
+    pub fn main_loop(
+        &self,
+        dict: &Dict,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<usize, PuzzleError> {
+        let mut lines = r.lines();
+        let mut mistakes = 0;
+        writeln!(w, "Secret: {}", self.secret)?;
+        for (index, row) in self.rows.iter().enumerate() {
+            loop {
+                write!(
+                    w,
+                    "Row {} of {} [{}]: ",
+                    index + 1,
+                    self.rows.len(),
+                    row.iter().join("")
+                )?;
+                w.flush()?;
+
+                match Attempt::inspect_input(
+                    lines
+                        .next()
+                        .ok_or(PuzzleError::UnexpectedEndOfFile)??
+                        .to_lowercase()
+                        .as_str(),
+                    &self.secret.into(),
+                    dict,
+                ) {
+                    Ok(attempt) if &states(&attempt) == row => {
+                        writeln!(w, "{attempt}")?;
+                        break;
+                    }
+                    Ok(attempt) => {
+                        mistakes += 1;
+                        writeln!(w, "{attempt} does not match")?;
+                    }
+                    Err(err) => {
+                        writeln!(w, "{}", PuzzleError::from(err))?;
+                    }
+                }
+            }
+        }
+        Ok(mistakes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attempt::AttemptError;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::io::Cursor;
+
+    #[test]
+    fn patterns_for_secret() {
+        let dict = Dict::default();
+        let patterns = super::patterns_for_secret(&dict, "сазан");
+        assert_eq!(
+            patterns.values().map(|words| words.len()).sum::<usize>(),
+            dict.words().len()
+        );
+        assert_eq!(
+            patterns.get(&vec![CharResult::Exact; 5]),
+            Some(&vec!["сазан"])
+        );
+    }
+
+    #[test]
+    fn generate_solvable() {
+        let dict = Dict::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let puzzle = ReversePuzzle::generate(&dict, "сазан", 4, difficulty, &mut rng).unwrap();
+            assert_eq!(puzzle.rows().len(), 4);
+            for (row, words) in puzzle.solutions().iter().enumerate() {
+                assert!(!words.is_empty());
+                for word in words {
+                    assert_eq!(puzzle.check(row, word, &dict), Ok(true));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generate_hard_has_fewer_solutions() {
+        let dict = Dict::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut max_solutions = |difficulty| {
+            ReversePuzzle::generate(&dict, "сазан", 3, difficulty, &mut rng)
+                .unwrap()
+                .solutions()
+                .iter()
+                .map(|words| words.len())
+                .max()
+                .unwrap()
+        };
+        assert!(max_solutions(Difficulty::Hard) <= max_solutions(Difficulty::Easy));
+    }
+
+    #[test]
+    fn generate_secret_not_in_dict() {
+        let dict = Dict::default();
+        assert_eq!(
+            ReversePuzzle::generate(
+                &dict,
+                "абвгд",
+                3,
+                Difficulty::Easy,
+                &mut StdRng::seed_from_u64(0)
+            )
+            .map(|_| ()),
+            Err(PuzzleError::SecretNotInDict)
+        );
+    }
+
+    #[test]
+    fn generate_not_enough_patterns() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        assert_eq!(
+            ReversePuzzle::generate(
+                &dict,
+                "сазан",
+                2,
+                Difficulty::Easy,
+                &mut StdRng::seed_from_u64(0)
+            )
+            .map(|_| ()),
+            Err(PuzzleError::NotEnoughPatterns)
+        );
+    }
+
+    #[test]
+    fn check_not_in_dict() {
+        let dict = Dict::from_iter(["казан", "сазан", "фазан"]);
+        let puzzle = ReversePuzzle::generate(
+            &dict,
+            "сазан",
+            1,
+            Difficulty::Easy,
+            &mut StdRng::seed_from_u64(0),
+        )
+        .unwrap();
+        assert_eq!(
+            puzzle.check(0, "абвгд", &dict),
+            Err(AttemptError::WordNotInDict.into())
+        );
+    }
+
+    #[test]
+    fn main_loop() {
+        let dict = Dict::from_iter(["казан", "сазан", "фазан"]);
+        let puzzle = ReversePuzzle::generate(
+            &dict,
+            "сазан",
+            1,
+            Difficulty::Easy,
+            &mut StdRng::seed_from_u64(0),
+        )
+        .unwrap();
+        let mut out = vec![];
+        let mut inp = Cursor::new("сазан\nказан\n");
+        assert_eq!(puzzle.main_loop(&dict, &mut inp, &mut out), Ok(1));
+        assert_eq!(
+            String::from_utf8(out).unwrap().as_str(),
+            "\
+            Secret: сазан\n\
+            Row 1 of 1 [ ++++]: с+а+з+а+н+ does not match\n\
+            Row 1 of 1 [ ++++]: к а+з+а+н+\n\
+            "
+        );
+    }
+}
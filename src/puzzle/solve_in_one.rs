@@ -0,0 +1,207 @@
+use crate::{puzzle::PuzzleError, solver, Attempt, CharPositions, Dict, GameFinishStatus};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use std::io::{BufRead, Write};
+
+const MAX_GENERATE_TRIALS: usize = 100;
+
+pub struct SolveInOnePuzzle {
+    secret: &'static str,
+    attempts: Vec<Attempt>,
+    candidates: Dict,
+}
+
+impl SolveInOnePuzzle {
+    pub fn generate(
+        dict: &Dict,
+        secret: &'static str,
+        prior_guesses: usize,
+        max_candidates: usize,
+        rng: &mut impl Rng,
+    ) -> Result<Self, PuzzleError> {
+        if !dict.word_in_dict(secret) {
+            return Err(PuzzleError::SecretNotInDict);
+        }
+        let char_positions = CharPositions::new(secret);
+        let inspect = |word| {
+            Attempt::inspect_input(word, &char_positions, dict).unwrap_or_else(|_| unreachable!())
+        };
+        let guesses = dict
+            .words()
+            .iter()
+            .copied()
+            .filter(|&word| word != secret)
+            .collect_vec();
+        (0..MAX_GENERATE_TRIALS)
+            .find_map(|_| {
+                let attempts = guesses
+                    .choose_multiple(rng, prior_guesses.checked_sub(1)?)
+                    .map(|word| inspect(word))
+                    .collect_vec();
+                let candidates = solver::candidates(dict, &attempts);
+                if candidates.words().len() <= max_candidates {
+                    return None;
+                }
+                let candidate_positions = candidates
+                    .words()
+                    .iter()
+                    .map(|&word| CharPositions::new(word))
+                    .collect_vec();
+                let last_guess = guesses
+                    .iter()
+                    .filter(|&&word| {
+                        let last_attempt = inspect(word);
+                        candidate_positions
+                            .iter()
+                            .filter(|char_positions| {
+                                Attempt::evaluate(word, char_positions) == last_attempt
+                            })
+                            .take(max_candidates + 1)
+                            .count()
+                            <= max_candidates
+                    })
+                    .collect_vec()
+                    .choose(rng)
+                    .copied()?;
+                let mut attempts = attempts;
+                attempts.push(inspect(last_guess));
+                Some(Self {
+                    secret,
+                    candidates: solver::candidates(&candidates, &attempts[attempts.len() - 1..]),
+                    attempts,
+                })
+            })
+            .ok_or(PuzzleError::GenerationFailed)
+    }
+
+    #[inline]
+    pub fn secret(&self) -> &'static str {
+        self.secret
+    }
+
+    #[inline]
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    #[inline]
+    pub fn candidates(&self) -> &Dict {
+        &self.candidates
+    }
+
+    pub fn main_loop(
+        &self,
+        dict: &Dict,
+        r: &mut impl BufRead,
+        w: &mut impl Write,
+    ) -> Result<GameFinishStatus, PuzzleError> {
+        let mut lines = r.lines();
+        for attempt in &self.attempts {
+            writeln!(w, "{attempt}")?;
+        }
+        loop {
+            write!(w, "Enter answer: ")?;
+            w.flush()?;
+
+            match Attempt::inspect_input(
+                lines
+                    .next()
+                    .ok_or(PuzzleError::UnexpectedEndOfFile)??
+                    .to_lowercase()
+                    .as_str(),
+                &self.secret.into(),
+                dict,
+            ) {
+                Ok(attempt) => {
+                    writeln!(w, "{attempt}")?;
+                    break Ok(if attempt.is_win_attempt() {
+                        GameFinishStatus::Win
+                    } else {
+                        GameFinishStatus::Fail
+                    });
+                }
+                Err(err) => {
+                    writeln!(w, "{}", PuzzleError::from(err))?;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::io::Cursor;
+
+    #[test]
+    fn generate_unique() {
+        let dict = Dict::default();
+        let puzzle =
+            SolveInOnePuzzle::generate(&dict, "сазан", 2, 1, &mut StdRng::seed_from_u64(0))
+                .unwrap();
+        assert_eq!(puzzle.attempts().len(), 2);
+        assert_eq!(puzzle.candidates().words(), ["сазан"]);
+        assert_eq!(
+            solver::candidates(&dict, puzzle.attempts()).words(),
+            ["сазан"]
+        );
+        assert!(
+            solver::candidates(&dict, &puzzle.attempts()[..1])
+                .words()
+                .len()
+                > 1
+        );
+    }
+
+    #[test]
+    fn generate_few_candidates() {
+        let dict = Dict::default();
+        let puzzle =
+            SolveInOnePuzzle::generate(&dict, "сазан", 3, 2, &mut StdRng::seed_from_u64(0))
+                .unwrap();
+        assert_eq!(puzzle.attempts().len(), 3);
+        assert!((1..=2).contains(&puzzle.candidates().words().len()));
+        assert!(puzzle.candidates().word_in_dict("сазан"));
+    }
+
+    #[test]
+    fn generate_secret_not_in_dict() {
+        let dict = Dict::default();
+        assert_eq!(
+            SolveInOnePuzzle::generate(&dict, "абвгд", 2, 1, &mut StdRng::seed_from_u64(0))
+                .map(|_| ()),
+            Err(PuzzleError::SecretNotInDict)
+        );
+    }
+
+    #[test]
+    fn generate_failed() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        assert_eq!(
+            SolveInOnePuzzle::generate(&dict, "сазан", 2, 1, &mut StdRng::seed_from_u64(0))
+                .map(|_| ()),
+            Err(PuzzleError::GenerationFailed)
+        );
+    }
+
+    #[test]
+    fn main_loop_win() {
+        let dict = Dict::default();
+        let puzzle =
+            SolveInOnePuzzle::generate(&dict, "сазан", 2, 1, &mut StdRng::seed_from_u64(0))
+                .unwrap();
+        let mut out = vec![];
+        let mut inp = Cursor::new("абвгд\nсазан\n");
+        assert_eq!(
+            puzzle.main_loop(&dict, &mut inp, &mut out),
+            Ok(GameFinishStatus::Win)
+        );
+        assert!(String::from_utf8(out).unwrap().ends_with(
+            "\
+            Enter answer: Attempt error: Word not in dictionary\n\
+            Enter answer: с+а+з+а+н+\n\
+            "
+        ));
+    }
+}
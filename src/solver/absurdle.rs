@@ -0,0 +1,181 @@
+use crate::{solver::PatternMatrix, Absurdle, Dict, GameFinishStatus, Pattern};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AbsurdleWin {
+    pub guesses: Vec<&'static str>,
+    // no shorter sequence exists, only known without a breadth limit
+    pub proven: bool,
+}
+
+impl AbsurdleWin {
+    // plays the guesses against the host, the last one and only it has to win
+    pub fn verify(&self, dict: &Dict) -> bool {
+        let mut game = Absurdle::new(dict, self.guesses.len());
+        self.guesses.iter().enumerate().all(|(i, guess)| {
+            game.try_input(guess).is_ok()
+                && (game.finish_status() == Some(GameFinishStatus::Win))
+                    == (i + 1 == self.guesses.len())
+        })
+    }
+}
+
+struct Search<'a> {
+    matrix: &'a PatternMatrix,
+    breadth: Option<usize>,
+    // candidate sets which can't be won within this many guesses
+    failed: HashMap<Vec<usize>, usize>,
+    counts: Vec<usize>,
+}
+
+impl<'a> Search<'a> {
+    fn new(matrix: &'a PatternMatrix, breadth: Option<usize>) -> Self {
+        Self {
+            matrix,
+            breadth,
+            failed: HashMap::new(),
+            counts: vec![0; Pattern::count(matrix.word_len())],
+        }
+    }
+
+    // the same choice as the host: the largest bucket, the lowest pattern on ties
+    fn host_bucket(&mut self, guess: usize, candidates: &[usize]) -> (Pattern, usize) {
+        self.counts.iter_mut().for_each(|count| *count = 0);
+        for &answer in candidates {
+            self.counts[self.matrix.get(guess, answer).index()] += 1;
+        }
+        let (pattern, &size) = self
+            .counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, count)| count)
+            .unwrap_or_else(|| unreachable!());
+        (Pattern(pattern as u16), size)
+    }
+
+    fn solve(&mut self, candidates: &[usize], guesses_left: usize) -> Option<Vec<usize>> {
+        if let &[answer] = candidates {
+            return (guesses_left > 0)
+                .then(|| self.matrix.guess_index(self.matrix.answers()[answer]))
+                .flatten()
+                .map(|guess| vec![guess]);
+        }
+        // with two words or more the host never concedes, so every guess has to shrink them
+        if guesses_left < 2
+            || self
+                .failed
+                .get(candidates)
+                .map_or(false, |&failed| failed >= guesses_left)
+        {
+            return None;
+        }
+        let mut moves = (0..self.matrix.guesses().len())
+            .map(|guess| {
+                let (pattern, size) = self.host_bucket(guess, candidates);
+                (size, guess, pattern)
+            })
+            .filter(|&(size, _, _)| size < candidates.len())
+            // a single guess left after this one wins only a single word
+            .filter(|&(size, _, _)| guesses_left > 2 || size == 1)
+            .collect::<Vec<_>>();
+        moves.sort_unstable();
+        let mut seen = HashSet::new();
+        for (_, guess, pattern) in moves {
+            if self.breadth.map_or(false, |breadth| seen.len() >= breadth) {
+                break;
+            }
+            let bucket = candidates
+                .iter()
+                .copied()
+                .filter(|&answer| self.matrix.get(guess, answer) == pattern)
+                .collect::<Vec<_>>();
+            if !seen.insert(bucket.clone()) {
+                continue;
+            }
+            if let Some(mut guesses) = self.solve(&bucket, guesses_left - 1) {
+                guesses.insert(0, guess);
+                return Some(guesses);
+            }
+        }
+        let failed = self.failed.entry(candidates.to_vec()).or_default();
+        *failed = guesses_left.max(*failed);
+        None
+    }
+}
+
+// iterative deepening, so the first sequence found is the shortest within the breadth
+pub fn absurdle_win(
+    matrix: &PatternMatrix,
+    max_guesses: usize,
+    breadth: Option<usize>,
+) -> Option<AbsurdleWin> {
+    let answers = (0..matrix.answers().len()).collect::<Vec<_>>();
+    let mut search = Search::new(matrix, breadth);
+    (1..=max_guesses).find_map(|guesses_left| {
+        search
+            .solve(&answers, guesses_left)
+            .map(|guesses| AbsurdleWin {
+                guesses: guesses
+                    .into_iter()
+                    .map(|guess| matrix.guesses()[guess])
+                    .collect(),
+                proven: breadth.is_none(),
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absurdle_win() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        let win = super::absurdle_win(&matrix, 6, None).unwrap();
+        // каска tells every word apart
+        assert_eq!(win.guesses.first(), Some(&"каска"));
+        assert_eq!(win.guesses.len(), 2);
+        assert!(win.proven);
+        assert!(win.verify(&dict));
+        assert_eq!(super::absurdle_win(&matrix, 1, None), None);
+
+        let dict = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        let win = super::absurdle_win(&matrix, 6, None).unwrap();
+        assert_eq!(win.guesses.len(), 3);
+        assert!(win.verify(&dict));
+        assert_eq!(super::absurdle_win(&matrix, 2, None), None);
+    }
+
+    #[test]
+    fn absurdle_win_breadth() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        let matrix = PatternMatrix::from_dict(&dict);
+        let exact = super::absurdle_win(&matrix, 6, None).unwrap();
+        assert!(exact.verify(&dict));
+        let greedy = super::absurdle_win(&matrix, 6, Some(1)).unwrap();
+        assert!(!greedy.proven);
+        assert!(greedy.verify(&dict));
+        assert!(greedy.guesses.len() >= exact.guesses.len());
+        assert_eq!(
+            super::absurdle_win(&matrix, exact.guesses.len() - 1, None),
+            None
+        );
+    }
+
+    #[test]
+    fn verify() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let win = |guesses: &[&'static str]| AbsurdleWin {
+            guesses: guesses.to_vec(),
+            proven: false,
+        };
+        assert!(win(&["казан", "сазан"]).verify(&dict));
+        assert!(!win(&["казан"]).verify(&dict));
+        assert!(!win(&["казан", "сазан", "сазан"]).verify(&dict));
+        assert!(!win(&["абвгд", "сазан"]).verify(&dict));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    solver::{auto_play, Solver},
+    Dict,
+};
+use itertools::Itertools;
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BenchGame {
+    pub secret: &'static str,
+    pub guesses: Vec<String>,
+    pub solved: bool,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BenchReport {
+    pub max_tries: usize,
+    pub games: Vec<BenchGame>,
+    pub elapsed: Duration,
+}
+
+pub fn bench(solver: &mut dyn Solver, answers: &Dict, max_tries: usize) -> BenchReport {
+    let started = Instant::now();
+    let games = answers
+        .words()
+        .iter()
+        .map(|&secret| {
+            let game_started = Instant::now();
+            let attempts = auto_play(solver, secret, max_tries);
+            let elapsed = game_started.elapsed();
+            BenchGame {
+                secret,
+                solved: attempts
+                    .last()
+                    .map_or(false, |attempt| attempt.is_win_attempt()),
+                guesses: attempts.iter().map(|attempt| attempt.word()).collect(),
+                elapsed,
+            }
+        })
+        .collect();
+    BenchReport {
+        max_tries,
+        games,
+        elapsed: started.elapsed(),
+    }
+}
+
+impl BenchReport {
+    #[inline]
+    pub fn solved(&self) -> usize {
+        self.games.iter().filter(|game| game.solved).count()
+    }
+
+    #[inline]
+    pub fn failed(&self) -> usize {
+        self.games.len() - self.solved()
+    }
+
+    // number of solved games by number of guesses, from 1 to max tries
+    pub fn distribution(&self) -> Vec<usize> {
+        let mut distribution = vec![0; self.max_tries];
+        for game in self.games.iter().filter(|game| game.solved) {
+            distribution[game.guesses.len() - 1] += 1;
+        }
+        distribution
+    }
+
+    pub fn average(&self) -> Option<f64> {
+        let solved = self.solved();
+        if solved == 0 {
+            return None;
+        }
+        let total = self
+            .games
+            .iter()
+            .filter(|game| game.solved)
+            .map(|game| game.guesses.len())
+            .sum::<usize>();
+        Some(total as f64 / solved as f64)
+    }
+
+    // most guesses of a solved game
+    pub fn worst_case(&self) -> Option<usize> {
+        self.games
+            .iter()
+            .filter(|game| game.solved)
+            .map(|game| game.guesses.len())
+            .max()
+    }
+
+    // the games of the given secrets only, e.g. of a trap family
+    pub fn subset(&self, secrets: &[&str]) -> Self {
+        let games = self
+            .games
+            .iter()
+            .filter(|game| secrets.contains(&game.secret))
+            .cloned()
+            .collect_vec();
+        Self {
+            max_tries: self.max_tries,
+            elapsed: games.iter().map(|game| game.elapsed).sum(),
+            games,
+        }
+    }
+
+    pub fn game(&self, secret: &str) -> Option<&BenchGame> {
+        self.games.iter().find(|game| game.secret == secret)
+    }
+
+    // failures first, then by number of guesses
+    pub fn worst(&self, count: usize) -> Vec<&BenchGame> {
+        self.games
+            .iter()
+            .sorted_by(|a, b| {
+                a.solved
+                    .cmp(&b.solved)
+                    .then_with(|| a.guesses.len().cmp(&b.guesses.len()).reverse())
+                    .then_with(|| a.secret.cmp(b.secret))
+            })
+            .take(count)
+            .collect()
+    }
+
+    pub fn write_csv(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "secret,solved,guesses,micros,path")?;
+        for game in &self.games {
+            writeln!(
+                w,
+                "{},{},{},{},{}",
+                game.secret,
+                game.solved,
+                game.guesses.len(),
+                game.elapsed.as_micros(),
+                game.guesses.iter().join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{FrequencySolver, SolverOptions, Strategy};
+
+    fn small_dict() -> Dict {
+        Dict::from_iter(["казан", "казна", "нотка", "сазан", "фазан", "шакал"])
+    }
+
+    #[test]
+    fn bench_all_strategies() {
+        let dict = small_dict();
+        for strategy in [Strategy::Frequency, Strategy::Entropy, Strategy::Minimax] {
+            let report = bench(strategy.solver(&dict).as_mut(), &dict, 6);
+            assert_eq!(report.games.len(), 6);
+            assert_eq!(report.failed(), 0);
+            assert_eq!(report.distribution().iter().sum::<usize>(), 6);
+            assert_eq!(report.distribution()[0], 1);
+            for game in &report.games {
+                assert_eq!(game.guesses.last().unwrap(), game.secret);
+            }
+        }
+    }
+
+    #[test]
+    fn failures() {
+        let dict = small_dict();
+        let report = bench(&mut FrequencySolver::new(&dict), &dict, 1);
+        assert_eq!(report.solved(), 1);
+        assert_eq!(report.failed(), 5);
+        assert_eq!(report.distribution(), [1]);
+        assert_eq!(report.average(), Some(1.0));
+        let worst = report.worst(2);
+        assert_eq!(worst.len(), 2);
+        assert!(worst.iter().all(|game| !game.solved));
+        assert!(report.worst(6).last().unwrap().solved);
+    }
+
+    #[test]
+    fn average() {
+        let dict = small_dict();
+        let report = bench(
+            Strategy::Minimax
+                .solver_with_options(
+                    &dict,
+                    SolverOptions {
+                        hard_mode: true,
+                        ..Default::default()
+                    },
+                )
+                .as_mut(),
+            &dict,
+            6,
+        );
+        let total = report
+            .distribution()
+            .iter()
+            .enumerate()
+            .map(|(i, count)| (i + 1) * count)
+            .sum::<usize>();
+        assert_eq!(report.average(), Some(total as f64 / 6.0));
+        assert_eq!(report.game("сазан").unwrap().secret, "сазан");
+        assert_eq!(report.game("шалаш"), None);
+        assert_eq!(
+            bench(&mut FrequencySolver::new(&dict), &dict, 0).average(),
+            None
+        );
+    }
+
+    #[test]
+    fn worst_case_and_subset() {
+        let dict = small_dict();
+        let report = bench(&mut FrequencySolver::new(&dict), &dict, 6);
+        assert_eq!(
+            report.worst_case(),
+            report.games.iter().map(|game| game.guesses.len()).max()
+        );
+        let subset = report.subset(&["сазан", "шакал", "шалаш"]);
+        assert_eq!(
+            subset.games.iter().map(|game| game.secret).collect_vec(),
+            ["сазан", "шакал"]
+        );
+        assert_eq!(subset.max_tries, 6);
+        assert_eq!(
+            bench(&mut FrequencySolver::new(&dict), &dict, 1).worst_case(),
+            Some(1)
+        );
+        assert_eq!(report.subset(&[]).worst_case(), None);
+    }
+
+    #[test]
+    fn write_csv() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let report = bench(&mut FrequencySolver::new(&dict), &dict, 6);
+        let mut out = vec![];
+        report.write_csv(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out
+            .lines()
+            .map(|line| line.split(',').collect_vec())
+            .collect_vec();
+        assert_eq!(lines[0], ["secret", "solved", "guesses", "micros", "path"]);
+        assert_eq!(lines.len(), 3);
+        for (line, game) in lines[1..].iter().zip(&report.games) {
+            assert_eq!(line[0], game.secret);
+            assert_eq!(line[1], "true");
+            assert_eq!(line[2], game.guesses.len().to_string());
+            assert_eq!(line[4], game.guesses.join(" "));
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{
+    puzzle::Difficulty,
+    solver::{bench, trap_families, RatingsError, Solver},
+    Dict,
+};
+use itertools::Itertools;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WordRating {
+    pub word: &'static str,
+    // over all strategies, a failed game counts one guess more than allowed
+    pub average: f64,
+    pub worst: usize,
+    // words differing in a single position
+    pub neighbors: usize,
+    // average bits of the letters by their frequency in the dictionary
+    pub letter_rarity: f64,
+}
+
+impl WordRating {
+    fn cmp_difficulty(&self, other: &Self) -> Ordering {
+        self.average
+            .total_cmp(&other.average)
+            .then_with(|| self.worst.cmp(&other.worst))
+            .then_with(|| self.neighbors.cmp(&other.neighbors))
+            .then_with(|| self.letter_rarity.total_cmp(&other.letter_rarity))
+            .then_with(|| self.word.cmp(other.word))
+    }
+}
+
+// easiest first
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WordRatings(Vec<WordRating>);
+
+fn neighbors(dict: &Dict) -> HashMap<&'static str, usize> {
+    let mut neighbors = HashMap::new();
+    for family in trap_families(dict, 2) {
+        for &word in &family.words {
+            *neighbors.entry(word).or_default() += family.words.len() - 1;
+        }
+    }
+    neighbors
+}
+
+fn letter_bits(dict: &Dict) -> HashMap<char, f64> {
+    let counts = dict.words().iter().flat_map(|word| word.chars()).counts();
+    let total = counts.values().sum::<usize>() as f64;
+    counts
+        .into_iter()
+        .map(|(ch, count)| (ch, (total / count as f64).log2()))
+        .collect()
+}
+
+impl WordRatings {
+    pub fn rate(dict: &Dict, solvers: &mut [Box<dyn Solver + '_>], max_tries: usize) -> Self {
+        let reports = solvers
+            .iter_mut()
+            .map(|solver| bench(solver.as_mut(), dict, max_tries))
+            .collect_vec();
+        let neighbors = neighbors(dict);
+        let letter_bits = letter_bits(dict);
+        Self::from_ratings(
+            dict.words()
+                .iter()
+                .enumerate()
+                .map(|(i, &word)| {
+                    let guesses = reports
+                        .iter()
+                        .map(|report| {
+                            let game = &report.games[i];
+                            if game.solved {
+                                game.guesses.len()
+                            } else {
+                                max_tries + 1
+                            }
+                        })
+                        .collect_vec();
+                    WordRating {
+                        word,
+                        average: guesses.iter().sum::<usize>() as f64 / guesses.len().max(1) as f64,
+                        worst: guesses.iter().copied().max().unwrap_or_default(),
+                        neighbors: neighbors.get(word).copied().unwrap_or_default(),
+                        letter_rarity: word.chars().map(|ch| letter_bits[&ch]).sum::<f64>()
+                            / word.chars().count().max(1) as f64,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    fn from_ratings(mut ratings: Vec<WordRating>) -> Self {
+        ratings.sort_by(WordRating::cmp_difficulty);
+        Self(ratings)
+    }
+
+    #[inline]
+    pub fn ratings(&self) -> &[WordRating] {
+        &self.0
+    }
+
+    pub fn rating(&self, word: &str) -> Option<&WordRating> {
+        self.0.iter().find(|rating| rating.word == word)
+    }
+
+    // a third of the words each, like the puzzle tiers
+    pub fn words(&self, difficulty: Difficulty) -> Vec<&'static str> {
+        let tier = match difficulty {
+            Difficulty::Easy => 0,
+            Difficulty::Medium => 1,
+            Difficulty::Hard => 2,
+        };
+        self.0[self.0.len() * tier / 3..self.0.len() * (tier + 1) / 3]
+            .iter()
+            .map(|rating| rating.word)
+            .collect()
+    }
+
+    pub fn write_csv(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "word,average,worst,neighbors,letter_rarity")?;
+        for rating in &self.0 {
+            writeln!(
+                w,
+                "{},{:.4},{},{},{:.4}",
+                rating.word, rating.average, rating.worst, rating.neighbors, rating.letter_rarity
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn parse_csv(s: &'static str) -> Result<Self, RatingsError> {
+        let mut ratings = vec![];
+        for (line_no, line) in s.lines().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_no = line_no + 1;
+            let Some((word, average, worst, neighbors, letter_rarity)) =
+                line.split(',').collect_tuple()
+            else {
+                return Err(RatingsError::UnexpectedColumns(line_no));
+            };
+            let invalid = |value: &str| RatingsError::InvalidValue(line_no, value.to_string());
+            ratings.push(WordRating {
+                word,
+                average: average.parse().map_err(|_| invalid(average))?,
+                worst: worst.parse().map_err(|_| invalid(worst))?,
+                neighbors: neighbors.parse().map_err(|_| invalid(neighbors))?,
+                letter_rarity: letter_rarity.parse().map_err(|_| invalid(letter_rarity))?,
+            });
+        }
+        Ok(Self::from_ratings(ratings))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RatingsError> {
+        // rated words live as long as the program, like the dictionary ones
+        Self::parse_csv(Box::leak(fs::read_to_string(path)?.into_boxed_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Strategy;
+
+    fn ratings(dict: &Dict) -> WordRatings {
+        let mut solvers =
+            [Strategy::Frequency, Strategy::Minimax].map(|strategy| strategy.solver(dict));
+        WordRatings::rate(dict, &mut solvers, 6)
+    }
+
+    #[test]
+    fn rate() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        let ratings = ratings(&dict);
+        assert_eq!(ratings.ratings().len(), dict.words().len());
+        assert!(ratings
+            .ratings()
+            .iter()
+            .tuple_windows()
+            .all(|(a, b)| a.cmp_difficulty(b) == Ordering::Less));
+        let hardest = ratings.ratings().last().unwrap();
+        assert!(hardest.worst >= ratings.ratings()[0].worst);
+        // all of байка гайка зайка лайка майка пайка сайка чайка шайка
+        assert_eq!(ratings.rating("майка").unwrap().neighbors, 8);
+        assert_eq!(ratings.rating("шланг").unwrap().neighbors, 0);
+    }
+
+    #[test]
+    fn words() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        let ratings = ratings(&dict);
+        let tiers = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+            .map(|difficulty| ratings.words(difficulty));
+        assert_eq!(
+            tiers.iter().map(Vec::len).sum::<usize>(),
+            dict.words().len()
+        );
+        assert!(tiers.iter().all(|tier| !tier.is_empty()));
+        let easy = ratings.rating(tiers[0][0]).unwrap();
+        let hard = ratings.rating(tiers[2][0]).unwrap();
+        assert_eq!(easy.cmp_difficulty(hard), Ordering::Less);
+    }
+
+    #[test]
+    fn csv() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let ratings = ratings(&dict);
+        let mut out = vec![];
+        ratings.write_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        assert!(csv.starts_with("word,average,worst,neighbors,letter_rarity\n"));
+        let parsed = WordRatings::parse_csv(Box::leak(csv.into_boxed_str())).unwrap();
+        assert_eq!(
+            parsed
+                .ratings()
+                .iter()
+                .map(|rating| rating.word)
+                .collect_vec(),
+            ratings
+                .ratings()
+                .iter()
+                .map(|rating| rating.word)
+                .collect_vec()
+        );
+        // сазан and фазан
+        assert_eq!(parsed.rating("казан").unwrap().neighbors, 2);
+        // в and я are in no other word
+        assert!(
+            parsed.rating("вязан").unwrap().letter_rarity
+                > parsed.rating("казан").unwrap().letter_rarity
+        );
+    }
+
+    #[test]
+    fn parse_csv_errors() {
+        assert_eq!(
+            WordRatings::parse_csv("word,average,worst,neighbors,letter_rarity\nказан,1,2,3\n"),
+            Err(RatingsError::UnexpectedColumns(2))
+        );
+        assert_eq!(
+            WordRatings::parse_csv("header\n\nказан,1.5,x,3,4.5\n"),
+            Err(RatingsError::InvalidValue(3, "x".to_string()))
+        );
+        assert_eq!(
+            WordRatings::parse_csv("header\n"),
+            Ok(WordRatings::default())
+        );
+    }
+
+    #[test]
+    fn load_missing_file() {
+        assert_eq!(
+            WordRatings::load("/nonexistent/ratings.csv"),
+            Err(RatingsError::IoError(io::ErrorKind::NotFound.into()))
+        );
+    }
+}
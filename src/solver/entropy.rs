@@ -0,0 +1,262 @@
+use crate::{solver::PatternMatrix, Dict, Pattern};
+use itertools::Itertools;
+use std::{cmp::Ordering, iter::zip};
+
+pub(crate) fn candidate_chars(candidates: &Dict) -> Vec<Vec<char>> {
+    candidates
+        .words()
+        .iter()
+        .map(|word| word.chars().collect())
+        .collect()
+}
+
+pub(crate) fn candidate_weights(candidates: &Dict) -> Vec<f64> {
+    let weights = candidates
+        .words()
+        .iter()
+        .map(|word| candidates.weight(word))
+        .collect_vec();
+    // nothing tells apart candidates never seen in the frequency list
+    if weights.iter().sum::<f64>() > 0.0 {
+        weights
+    } else {
+        vec![1.0; weights.len()]
+    }
+}
+
+pub(crate) fn bucket_sizes(guess: &str, candidate_chars: &[Vec<char>]) -> Vec<usize> {
+    let guess = guess.chars().collect_vec();
+    let mut sizes = vec![0; Pattern::count(guess.len())];
+    for answer in candidate_chars {
+        sizes[Pattern::evaluate(&guess, answer).index()] += 1;
+    }
+    sizes
+}
+
+pub(crate) fn bucket_weights(
+    guess: &str,
+    candidate_chars: &[Vec<char>],
+    candidate_weights: &[f64],
+) -> Vec<f64> {
+    let guess = guess.chars().collect_vec();
+    let mut weights = vec![0.0; Pattern::count(guess.len())];
+    for (answer, weight) in zip(candidate_chars, candidate_weights) {
+        weights[Pattern::evaluate(&guess, answer).index()] += weight;
+    }
+    weights
+}
+
+fn candidate_indices(matrix: Option<&PatternMatrix>, candidates: &Dict) -> Option<Vec<usize>> {
+    matrix.and_then(|matrix| {
+        candidates
+            .words()
+            .iter()
+            .map(|word| matrix.answer_index(word))
+            .collect()
+    })
+}
+
+pub(crate) fn bucket_sizer<'a>(
+    matrix: Option<&'a PatternMatrix>,
+    candidates: &Dict,
+) -> impl Fn(&str) -> Vec<usize> + 'a {
+    let candidate_chars = candidate_chars(candidates);
+    let candidate_indices = candidate_indices(matrix, candidates);
+    move |guess| {
+        if let (Some(matrix), Some(candidate_indices)) = (matrix, &candidate_indices) {
+            if let Some(guess) = matrix.guess_index(guess) {
+                return matrix.bucket_sizes(guess, candidate_indices);
+            }
+        }
+        bucket_sizes(guess, &candidate_chars)
+    }
+}
+
+pub(crate) fn bucket_weigher<'a>(
+    matrix: Option<&'a PatternMatrix>,
+    candidates: &Dict,
+) -> impl Fn(&str) -> Vec<f64> + 'a {
+    let candidate_chars = candidate_chars(candidates);
+    let candidate_weights = candidate_weights(candidates);
+    let candidate_indices = candidate_indices(matrix, candidates);
+    move |guess| {
+        if let (Some(matrix), Some(candidate_indices)) = (matrix, &candidate_indices) {
+            if let Some(guess) = matrix.guess_index(guess) {
+                return matrix.bucket_weights(guess, candidate_indices, &candidate_weights);
+            }
+        }
+        bucket_weights(guess, &candidate_chars, &candidate_weights)
+    }
+}
+
+// candidates go first, likelier ones earlier
+pub(crate) fn cmp_as_answer(candidates: &Dict, a: &str, b: &str) -> Ordering {
+    let answer_weight = |word: &str| {
+        candidates
+            .word_in_dict(word)
+            .then(|| candidates.weight(word))
+    };
+    match (answer_weight(a), answer_weight(b)) {
+        (Some(a), Some(b)) => a.total_cmp(&b).reverse(),
+        (a, b) => a.is_some().cmp(&b.is_some()).reverse(),
+    }
+}
+
+pub fn entropy(guess: &str, candidates: &Dict) -> f64 {
+    entropy_of_weights(&bucket_weights(
+        guess,
+        &candidate_chars(candidates),
+        &candidate_weights(candidates),
+    ))
+}
+
+pub(crate) fn entropy_of_weights(weights: &[f64]) -> f64 {
+    let total = weights.iter().sum::<f64>();
+    weights
+        .iter()
+        .filter(|&&weight| weight > 0.0)
+        .map(|weight| weight / total * (total / weight).log2())
+        .sum()
+}
+
+pub fn rank_by_entropy(guesses: &Dict, candidates: &Dict) -> Vec<(&'static str, f64)> {
+    rank(guesses, candidates, bucket_weigher(None, candidates))
+}
+
+pub fn rank_by_entropy_with_matrix(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    candidates: &Dict,
+) -> Vec<(&'static str, f64)> {
+    rank(
+        guesses,
+        candidates,
+        bucket_weigher(Some(matrix), candidates),
+    )
+}
+
+fn rank(
+    guesses: &Dict,
+    candidates: &Dict,
+    bucket_weights: impl Fn(&str) -> Vec<f64>,
+) -> Vec<(&'static str, f64)> {
+    guesses
+        .words()
+        .iter()
+        .map(|&guess| (guess, entropy_of_weights(&bucket_weights(guess))))
+        .sorted_by(|(a_guess, a_entropy), (b_guess, b_entropy)| {
+            a_entropy
+                .total_cmp(b_entropy)
+                .reverse()
+                .then_with(|| cmp_as_answer(candidates, a_guess, b_guess))
+                .then_with(|| a_guess.cmp(b_guess))
+        })
+        .collect()
+}
+
+pub fn suggest_word_by_entropy(guesses: &Dict, candidates: &Dict) -> Option<&'static str> {
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => rank_by_entropy(guesses, candidates)
+            .first()
+            .map(|&(word, _)| word),
+    }
+}
+
+pub fn suggest_word_by_entropy_with_matrix(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    candidates: &Dict,
+) -> Option<&'static str> {
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => rank_by_entropy_with_matrix(matrix, guesses, candidates)
+            .first()
+            .map(|&(word, _)| word),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entropy() {
+        let candidates = Dict::from_iter(["казан", "сазан", "фазан", "вязан"]);
+        assert_eq!(super::entropy("каска", &candidates), 2.0);
+        assert_eq!(super::entropy("казан", &candidates), 1.5);
+        assert_eq!(super::entropy("сазан", &Dict::from_iter(["сазан"])), 0.0);
+    }
+
+    #[test]
+    fn rank_by_entropy() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let ranked = super::rank_by_entropy(&dict, &candidates);
+        assert_eq!(ranked.len(), 5);
+        assert!(ranked.iter().tuple_windows().all(|((_, a), (_, b))| a >= b));
+        assert_eq!(ranked.first(), Some(&("каска", 2.0)));
+    }
+
+    #[test]
+    fn suggest_word_by_entropy() {
+        let dict = Dict::default();
+        let candidates = Dict::from_iter(["казан", "сазан", "фазан"]);
+        let suggestion = super::suggest_word_by_entropy(&dict, &candidates).unwrap();
+        assert!((super::entropy(suggestion, &candidates) - 3f64.log2()).abs() < 1e-9);
+        assert_eq!(
+            super::suggest_word_by_entropy(&dict, &Dict::from_iter(["сазан"])),
+            Some("сазан")
+        );
+        assert_eq!(super::suggest_word_by_entropy(&dict, &Dict::empty()), None);
+    }
+
+    #[test]
+    fn weighted_entropy() {
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]).with_weights([
+            ("казан", 2.0),
+            ("сазан", 1.0),
+            ("фазан", 1.0),
+        ]);
+        // вязан is never seen, so каска splits 3 likely words, казан splits 2 from 2
+        assert_eq!(super::entropy("каска", &candidates), 1.5);
+        assert_eq!(super::entropy("казан", &candidates), 1.0);
+        let unseen = Dict::from_iter(["казан", "сазан"]).with_weights([]);
+        assert_eq!(super::entropy("казан", &unseen), 1.0);
+    }
+
+    #[test]
+    fn rank_prefers_likely_answers() {
+        let candidates = Dict::from_iter(["казан", "сазан"]);
+        assert_eq!(
+            super::rank_by_entropy(&candidates, &candidates)[0],
+            ("казан", 1.0)
+        );
+        let candidates = candidates.with_weights([("казан", 1.0), ("сазан", 3.0)]);
+        let ranked = super::rank_by_entropy(&candidates, &candidates);
+        assert_eq!(ranked[0].0, "сазан");
+        assert!((ranked[0].1 - super::entropy("казан", &candidates)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rank_by_entropy_with_matrix() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        assert_eq!(
+            super::rank_by_entropy_with_matrix(&matrix, &dict, &candidates),
+            super::rank_by_entropy(&dict, &candidates)
+        );
+        let outside = Dict::from_iter(["вязан", "казан", "нотка"]);
+        assert_eq!(
+            super::rank_by_entropy_with_matrix(&matrix, &outside, &candidates),
+            super::rank_by_entropy(&outside, &candidates)
+        );
+        assert_eq!(
+            super::suggest_word_by_entropy_with_matrix(&matrix, &dict, &candidates),
+            Some("каска")
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    io,
+};
+
+#[derive(Debug)]
+pub enum PatternCacheError {
+    BadHeader,
+    StaleDict,
+    DictMismatch,
+    InvalidPattern(u16),
+    IoError(io::Error),
+}
+
+#[cfg(test)]
+impl PartialEq for PatternCacheError {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::BadHeader => matches!(other, Self::BadHeader),
+            Self::StaleDict => matches!(other, Self::StaleDict),
+            Self::DictMismatch => matches!(other, Self::DictMismatch),
+            Self::InvalidPattern(code) => {
+                matches!(other, Self::InvalidPattern(other_code) if code == other_code)
+            }
+            Self::IoError(io) => {
+                matches!(other, Self::IoError(other_io) if io.kind() == other_io.kind())
+            }
+        }
+    }
+}
+
+impl Error for PatternCacheError {}
+
+impl From<io::Error> for PatternCacheError {
+    #[inline]
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl Display for PatternCacheError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadHeader => write!(f, "Not a pattern cache file of this version"),
+            Self::StaleDict => write!(f, "Pattern cache was built for another dictionary"),
+            Self::DictMismatch => write!(f, "Pattern matrix was built for another dictionary"),
+            Self::InvalidPattern(code) => write!(f, "Invalid pattern in cache: {code}"),
+            Self::IoError(err) => write!(f, "I/O Error: {err}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RatingsError {
+    UnexpectedColumns(usize),
+    InvalidValue(usize, String),
+    IoError(io::Error),
+}
+
+#[cfg(test)]
+impl PartialEq for RatingsError {
+    fn eq(&self, other: &Self) -> bool {
+        match self {
+            Self::UnexpectedColumns(line) => {
+                matches!(other, Self::UnexpectedColumns(other_line) if line == other_line)
+            }
+            Self::InvalidValue(line, value) => {
+                matches!(other, Self::InvalidValue(other_line, other_value)
+                    if line == other_line && value == other_value)
+            }
+            Self::IoError(io) => {
+                matches!(other, Self::IoError(other_io) if io.kind() == other_io.kind())
+            }
+        }
+    }
+}
+
+impl Error for RatingsError {}
+
+impl From<io::Error> for RatingsError {
+    #[inline]
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl Display for RatingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedColumns(line) => {
+                write!(
+                    f,
+                    "Line {line}: expected word, average, worst, neighbors and letter rarity"
+                )
+            }
+            Self::InvalidValue(line, value) => write!(f, "Line {line}: invalid value '{value}'"),
+            Self::IoError(err) => write!(f, "I/O Error: {err}"),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{
+    solver::{
+        entropy::{
+            bucket_sizer, bucket_sizes, bucket_weigher, bucket_weights, candidate_chars,
+            candidate_weights, cmp_as_answer,
+        },
+        PatternMatrix,
+    },
+    Dict, Pattern,
+};
+use itertools::Itertools;
+use std::iter::zip;
+
+// candidates left on average, a win leaves none
+fn cost_of_buckets(guess: &str, sizes: &[usize], weights: &[f64]) -> f64 {
+    let total = weights.iter().sum::<f64>();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let win = Pattern::win(guess.chars().count()).index();
+    zip(sizes, weights)
+        .enumerate()
+        .filter(|&(pattern, _)| pattern != win)
+        .map(|(_, (&size, weight))| size as f64 * weight)
+        .sum::<f64>()
+        / total
+}
+
+pub fn expected_cost(guess: &str, candidates: &Dict) -> f64 {
+    let candidate_chars = candidate_chars(candidates);
+    cost_of_buckets(
+        guess,
+        &bucket_sizes(guess, &candidate_chars),
+        &bucket_weights(guess, &candidate_chars, &candidate_weights(candidates)),
+    )
+}
+
+pub fn rank_by_expected_cost(guesses: &Dict, candidates: &Dict) -> Vec<(&'static str, f64)> {
+    rank(
+        guesses,
+        candidates,
+        bucket_sizer(None, candidates),
+        bucket_weigher(None, candidates),
+    )
+}
+
+pub fn rank_by_expected_cost_with_matrix(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    candidates: &Dict,
+) -> Vec<(&'static str, f64)> {
+    rank(
+        guesses,
+        candidates,
+        bucket_sizer(Some(matrix), candidates),
+        bucket_weigher(Some(matrix), candidates),
+    )
+}
+
+fn rank(
+    guesses: &Dict,
+    candidates: &Dict,
+    bucket_sizes: impl Fn(&str) -> Vec<usize>,
+    bucket_weights: impl Fn(&str) -> Vec<f64>,
+) -> Vec<(&'static str, f64)> {
+    guesses
+        .words()
+        .iter()
+        .map(|&guess| {
+            (
+                guess,
+                cost_of_buckets(guess, &bucket_sizes(guess), &bucket_weights(guess)),
+            )
+        })
+        .sorted_by(|(a_guess, a_cost), (b_guess, b_cost)| {
+            a_cost
+                .total_cmp(b_cost)
+                .then_with(|| cmp_as_answer(candidates, a_guess, b_guess))
+                .then_with(|| a_guess.cmp(b_guess))
+        })
+        .collect()
+}
+
+pub fn suggest_word_by_expected_cost(guesses: &Dict, candidates: &Dict) -> Option<&'static str> {
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => rank_by_expected_cost(guesses, candidates)
+            .first()
+            .map(|&(word, _)| word),
+    }
+}
+
+pub fn suggest_word_by_expected_cost_with_matrix(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    candidates: &Dict,
+) -> Option<&'static str> {
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => rank_by_expected_cost_with_matrix(matrix, guesses, candidates)
+            .first()
+            .map(|&(word, _)| word),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_cost() {
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        assert_eq!(super::expected_cost("каска", &candidates), 1.0);
+        assert_eq!(super::expected_cost("казан", &candidates), 1.25);
+        assert_eq!(super::expected_cost("сазан", &Dict::empty()), 0.0);
+        let candidates = candidates.with_weights([("казан", 2.0), ("сазан", 1.0), ("фазан", 1.0)]);
+        assert_eq!(super::expected_cost("каска", &candidates), 1.0);
+        assert_eq!(super::expected_cost("казан", &candidates), 1.0);
+    }
+
+    #[test]
+    fn rank_by_expected_cost() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let ranked = super::rank_by_expected_cost(&dict, &candidates);
+        assert_eq!(ranked.len(), 5);
+        assert!(ranked.iter().tuple_windows().all(|((_, a), (_, b))| a <= b));
+        assert_eq!(ranked.first(), Some(&("каска", 1.0)));
+        let matrix = PatternMatrix::from_dict(&dict);
+        assert_eq!(
+            super::rank_by_expected_cost_with_matrix(&matrix, &dict, &candidates),
+            ranked
+        );
+    }
+
+    #[test]
+    fn suggest_word_by_expected_cost() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        assert_eq!(
+            super::suggest_word_by_expected_cost(&dict, &candidates),
+            Some("каска")
+        );
+        // a likely answer is worth guessing even when it splits the rest worse
+        let candidates = candidates.with_weights([
+            ("вязан", 1.0),
+            ("казан", 10.0),
+            ("сазан", 1.0),
+            ("фазан", 1.0),
+        ]);
+        assert_eq!(
+            super::suggest_word_by_expected_cost(&dict, &candidates),
+            Some("казан")
+        );
+        assert_eq!(
+            super::suggest_word_by_expected_cost(&dict, &Dict::empty()),
+            None
+        );
+    }
+}
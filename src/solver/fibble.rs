@@ -0,0 +1,332 @@
+use crate::{
+    solver::{
+        entropy::{candidate_weights, cmp_as_answer, entropy_of_weights},
+        PatternCacheError, PatternMatrix, Solver,
+    },
+    Attempt, Dict, Pattern,
+};
+use itertools::Itertools;
+use std::{cell::Cell, cmp::Ordering, collections::HashSet};
+
+pub const FIBBLE_LIE_PROBABILITY: f64 = 1.0;
+
+fn differing_tiles(a: Pattern, b: Pattern, word_len: usize) -> usize {
+    let (Pattern(mut a), Pattern(mut b)) = (a, b);
+    (0..word_len)
+        .filter(|_| {
+            let differs = a % 3 != b % 3;
+            a /= 3;
+            b /= 3;
+            differs
+        })
+        .count()
+}
+
+// every row lies with the given probability, in a single tile shown as either other color
+pub struct FibbleSolver<'a> {
+    dict: &'a Dict,
+    matrix: PatternMatrix,
+    lie_probability: f64,
+    // dict indices of words not ruled out, with their prior times the likelihood of the rows
+    posterior: Vec<(usize, f64)>,
+    candidates: Dict,
+    attempts: usize,
+    opening: Cell<Option<&'static str>>,
+}
+
+impl<'a> FibbleSolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_lie_probability(dict, FIBBLE_LIE_PROBABILITY)
+    }
+
+    #[inline]
+    pub fn with_lie_probability(dict: &'a Dict, lie_probability: f64) -> Self {
+        Self::with_matrix_unchecked(dict, lie_probability, PatternMatrix::from_dict(dict))
+    }
+
+    pub fn with_matrix(
+        dict: &'a Dict,
+        lie_probability: f64,
+        matrix: PatternMatrix,
+    ) -> Result<Self, PatternCacheError> {
+        matrix.check_dict(dict)?;
+        Ok(Self::with_matrix_unchecked(dict, lie_probability, matrix))
+    }
+
+    fn with_matrix_unchecked(dict: &'a Dict, lie_probability: f64, matrix: PatternMatrix) -> Self {
+        debug_assert!((0.0..=1.0).contains(&lie_probability));
+        let mut solver = Self {
+            dict,
+            matrix,
+            lie_probability,
+            posterior: vec![],
+            candidates: Dict::empty(),
+            attempts: 0,
+            opening: Cell::new(None),
+        };
+        solver.start();
+        solver
+    }
+
+    #[inline]
+    pub fn lie_probability(&self) -> f64 {
+        self.lie_probability
+    }
+
+    fn likelihood(&self, truth: Pattern, shown: Pattern) -> f64 {
+        let word_len = self.matrix.word_len();
+        match differing_tiles(truth, shown, word_len) {
+            0 => 1.0 - self.lie_probability,
+            1 => self.lie_probability / (2 * word_len) as f64,
+            _ => 0.0,
+        }
+    }
+
+    fn updated_posterior(&self, attempt: &Attempt) -> Vec<(usize, f64)> {
+        let guess = attempt.word();
+        let guess_chars = guess.chars().collect_vec();
+        let guess_index = self.matrix.guess_index(&guess);
+        let shown = Pattern::from(attempt);
+        self.posterior
+            .iter()
+            .map(|&(answer, weight)| {
+                let truth = match guess_index {
+                    Some(guess) => self.matrix.get(guess, answer),
+                    None => Pattern::evaluate(
+                        &guess_chars,
+                        &self.dict.words()[answer].chars().collect_vec(),
+                    ),
+                };
+                (answer, weight * self.likelihood(truth, shown))
+            })
+            .filter(|&(_, weight)| weight > 0.0)
+            .collect()
+    }
+
+    fn update_candidates(&mut self) {
+        let words = self.dict.words();
+        let kept = self
+            .posterior
+            .iter()
+            .map(|&(answer, _)| words[answer])
+            .collect::<HashSet<_>>();
+        let mut candidates = self.dict.clone();
+        candidates.retain(|word| kept.contains(word));
+        self.candidates = candidates.with_weights(
+            self.posterior
+                .iter()
+                .map(|&(answer, weight)| (words[answer], weight)),
+        );
+    }
+
+    // entropy of the shown feedback, the lie adds the same uncertainty for every answer
+    fn entropy(&self, guess: usize) -> f64 {
+        let word_len = self.matrix.word_len();
+        let spread = self.lie_probability / (2 * word_len) as f64;
+        let mut weights = vec![0.0; Pattern::count(word_len)];
+        for &(answer, weight) in &self.posterior {
+            let truth = self.matrix.get(guess, answer).index();
+            weights[truth] += weight * (1.0 - self.lie_probability);
+            let mut place = 1;
+            for _ in 0..word_len {
+                let state = truth / place % 3;
+                for other in (0..3).filter(|&other| other != state) {
+                    weights[truth - state * place + other * place] += weight * spread;
+                }
+                place *= 3;
+            }
+        }
+        entropy_of_weights(&weights)
+    }
+
+    fn cmp_ranked(
+        &self,
+        (a_guess, a_entropy): &(&str, f64),
+        (b_guess, b_entropy): &(&str, f64),
+    ) -> Ordering {
+        a_entropy
+            .total_cmp(b_entropy)
+            .reverse()
+            .then_with(|| cmp_as_answer(&self.candidates, a_guess, b_guess))
+            .then_with(|| a_guess.cmp(b_guess))
+    }
+
+    fn suggest_uncached(&self) -> Option<&'static str> {
+        match self.candidates.words() {
+            [] => None,
+            [word] => Some(word),
+            _ => (0..self.dict.words().len())
+                .map(|guess| (self.dict.words()[guess], self.entropy(guess)))
+                .min_by(|a, b| self.cmp_ranked(a, b))
+                .map(|(guess, _)| guess),
+        }
+    }
+}
+
+impl Solver for FibbleSolver<'_> {
+    fn start(&mut self) {
+        self.posterior = candidate_weights(self.dict)
+            .into_iter()
+            .enumerate()
+            .collect();
+        self.candidates = self.dict.clone();
+        self.attempts = 0;
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.posterior = self.updated_posterior(attempt);
+        self.update_candidates();
+        self.attempts += 1;
+    }
+
+    fn suggest(&self) -> Option<&'static str> {
+        if self.attempts > 0 {
+            return self.suggest_uncached();
+        }
+        if self.opening.get().is_none() {
+            self.opening.set(self.suggest_uncached());
+        }
+        self.opening.get()
+    }
+
+    #[inline]
+    fn candidates(&self) -> &Dict {
+        &self.candidates
+    }
+
+    fn is_consistent(&self, attempt: &Attempt) -> bool {
+        !self.updated_posterior(attempt).is_empty()
+    }
+
+    // by the entropy of the shown feedback, lies included
+    fn rank(&self, guesses: &Dict) -> Vec<(&'static str, f64)> {
+        guesses
+            .words()
+            .iter()
+            .filter_map(|&guess| {
+                let index = self.matrix.guess_index(guess)?;
+                Some((guess, self.entropy(index)))
+            })
+            .sorted_by(|a, b| self.cmp_ranked(a, b))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solver, CharPositions, CharResult};
+
+    fn dict() -> Dict {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        dict
+    }
+
+    // feedback for the answer with a lie about the tile at the position
+    fn lying(guess: &str, answer: &str, pos: usize) -> Attempt {
+        let truth = Pattern::from(&Attempt::evaluate(guess, &CharPositions::new(answer)));
+        let mut states = truth.states(guess.chars().count());
+        states[pos] = match states[pos] {
+            CharResult::Exact => CharResult::Unsuccessful,
+            _ => CharResult::Exact,
+        };
+        Pattern::from_states(states.into_iter()).to_attempt(guess)
+    }
+
+    #[test]
+    fn differing_tiles() {
+        let pattern = |attempt: &str| Pattern::from(&attempt.parse::<Attempt>().unwrap());
+        assert_eq!(
+            super::differing_tiles(pattern("к+а+з+а+н+"), pattern("к+а+з+а+н+"), 5),
+            0
+        );
+        assert_eq!(
+            super::differing_tiles(pattern("к+а+з+а+н+"), pattern("к+а?з+а+н+"), 5),
+            1
+        );
+        assert_eq!(
+            super::differing_tiles(pattern("к а з а н "), pattern("к+а+з+а+н+"), 5),
+            5
+        );
+    }
+
+    #[test]
+    fn keeps_answer_despite_lies() {
+        let dict = dict();
+        let mut solver = FibbleSolver::new(&dict);
+        for (pos, guess) in ["майка", "шалаш", "шпала"].into_iter().enumerate() {
+            solver.observe(&lying(guess, "шайка", pos));
+            assert!(solver.candidates().word_in_dict("шайка"));
+        }
+        assert!(solver.candidates().words().len() < dict.words().len());
+        for &word in solver.candidates().words() {
+            assert!(solver.candidates().weight(word) > 0.0);
+        }
+    }
+
+    #[test]
+    fn truthful_rows_rule_out_under_fibble() {
+        let dict = dict();
+        let mut solver = FibbleSolver::new(&dict);
+        let attempt = Attempt::evaluate("майка", &CharPositions::new("майка"));
+        assert!(solver.is_consistent(&attempt));
+        solver.observe(&attempt);
+        // exactly one lie means the shown feedback itself is never the truth
+        assert!(!solver.candidates().word_in_dict("майка"));
+
+        let mut solver = FibbleSolver::with_lie_probability(&dict, 0.5);
+        solver.observe(&attempt);
+        let candidates = solver.candidates();
+        assert!(candidates.word_in_dict("майка"));
+        assert!(candidates.words().iter().all(|&word| {
+            word == "майка" || candidates.weight(word) < candidates.weight("майка")
+        }));
+    }
+
+    #[test]
+    fn suggest() {
+        let dict = dict();
+        let mut solver = FibbleSolver::new(&dict);
+        let opening = solver.suggest().unwrap();
+        assert_eq!(solver.suggest(), Some(opening));
+        let best = solver.entropy(solver.matrix.guess_index(opening).unwrap());
+        assert!((0..dict.words().len()).all(|guess| solver.entropy(guess) <= best));
+        solver.start();
+        assert_eq!(solver.suggest(), Some(opening));
+        solver.observe(&"ш+а+й+к+а ".parse().unwrap());
+        assert_eq!(solver.candidates().words(), ["шайка", "шанкр", "шашки"]);
+        solver.observe(&lying("шашки", "шайка", 0));
+        assert_eq!(solver.candidates().words(), ["шайка", "шанкр"]);
+        solver.observe(&lying("шанкр", "шайка", 0));
+        assert_eq!(solver.candidates().words(), ["шайка"]);
+        assert_eq!(solver.suggest(), Some("шайка"));
+    }
+
+    #[test]
+    fn rank() {
+        let dict = dict();
+        let mut solver = FibbleSolver::new(&dict);
+        solver.observe(&lying("майка", "шайка", 0));
+        let ranked = solver.rank(&dict);
+        assert_eq!(ranked.len(), dict.words().len());
+        assert_eq!(ranked.first().map(|&(word, _)| word), solver.suggest());
+        for &(word, entropy) in &ranked {
+            assert_eq!(
+                entropy,
+                solver.entropy(solver.matrix.guess_index(word).unwrap())
+            );
+        }
+        // the noise-free ranking of the same candidates differs
+        assert_ne!(ranked, solver::rank_by_entropy(&dict, solver.candidates()));
+    }
+
+    #[test]
+    fn is_consistent() {
+        let dict = Dict::from_iter(["казан", "сазан"]);
+        let solver = FibbleSolver::new(&dict);
+        assert!(solver.is_consistent(&"к+а+з+а+н ".parse().unwrap()));
+        assert!(!solver.is_consistent(&"к а з а н ".parse().unwrap()));
+    }
+}
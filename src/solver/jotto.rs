@@ -0,0 +1,87 @@
+use crate::{CharPositions, Dict, JottoAttempt};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub fn candidates<'a>(dict: &Dict, attempts: impl IntoIterator<Item = &'a JottoAttempt>) -> Dict {
+    let attempts = attempts.into_iter().collect_vec();
+    let mut dict = dict.clone();
+    dict.retain(|word| {
+        let char_positions = CharPositions::new(word);
+        attempts
+            .iter()
+            .all(|&attempt| &JottoAttempt::evaluate(&attempt.word, &char_positions) == attempt)
+    });
+    dict
+}
+
+pub fn suggest_word(candidates: &Dict) -> Option<&'static str> {
+    let char_positions = candidates
+        .words()
+        .iter()
+        .map(|&word| CharPositions::new(word))
+        .collect_vec();
+    candidates
+        .words()
+        .iter()
+        .map(|&guess| {
+            let buckets = char_positions
+                .iter()
+                .map(|char_positions| {
+                    let JottoAttempt {
+                        exact, misplaced, ..
+                    } = JottoAttempt::evaluate(guess, char_positions);
+                    (exact, misplaced)
+                })
+                .fold(HashMap::<_, usize>::new(), |mut acc, counts| {
+                    *acc.entry(counts).or_default() += 1;
+                    acc
+                });
+            let max_bucket = buckets.values().copied().max().unwrap_or_default();
+            (guess, buckets.len(), max_bucket)
+        })
+        .min_by(
+            |(a_guess, a_buckets, a_max_bucket), (b_guess, b_buckets, b_max_bucket)| {
+                a_buckets
+                    .cmp(b_buckets)
+                    .reverse()
+                    .then_with(|| a_max_bucket.cmp(b_max_bucket))
+                    .then_with(|| a_guess.cmp(b_guess))
+            },
+        )
+        .map(|(guess, _, _)| guess)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates() {
+        let dict = Dict::default();
+        let attempts = [JottoAttempt::evaluate("казна", &"сазан".into())];
+        let candidates = super::candidates(&dict, &attempts);
+        assert!(candidates.word_in_dict("сазан"));
+        assert!(!candidates.word_in_dict("казна"));
+        for &word in candidates.words() {
+            assert_eq!(JottoAttempt::evaluate("казна", &word.into()), attempts[0]);
+        }
+    }
+
+    #[test]
+    fn candidates_empty() {
+        let dict = Dict::default();
+        let attempt = JottoAttempt {
+            word: "казна".to_string(),
+            exact: 5,
+            misplaced: 1,
+        };
+        assert_eq!(super::candidates(&dict, &[attempt]), Dict::empty());
+    }
+
+    #[test]
+    fn suggest_word() {
+        let dict = Dict::from_iter(["казан", "сазан", "фазан", "казна"]);
+        assert_eq!(super::suggest_word(&dict), Some("казан"));
+        assert_eq!(super::suggest_word(&Dict::empty()), None);
+    }
+}
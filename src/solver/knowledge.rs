@@ -0,0 +1,318 @@
+use crate::{Attempt, CharPos, CharResult, Dict};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Knowledge {
+    word_len: Option<usize>,
+    exact: HashMap<CharPos, char>,
+    misplaced: HashMap<char, HashSet<CharPos>>,
+    denied_poses: HashMap<char, HashSet<CharPos>>,
+    min_counts: HashMap<char, usize>,
+    max_counts: HashMap<char, usize>,
+    contradiction: bool,
+}
+
+impl Knowledge {
+    pub fn from_attempts<'a>(attempts: impl IntoIterator<Item = &'a Attempt>) -> Self {
+        attempts
+            .into_iter()
+            .fold(Self::default(), |mut knowledge, attempt| {
+                knowledge.observe(attempt);
+                knowledge
+            })
+    }
+
+    pub fn observe(&mut self, attempt: &Attempt) {
+        let Attempt(attempt_chars) = attempt;
+        if *self.word_len.get_or_insert(attempt_chars.len()) != attempt_chars.len() {
+            self.contradiction = true;
+        }
+        let mut found_counts = HashMap::<_, usize>::new();
+        let mut unsuccessful_chars = HashSet::new();
+        for (pos, attempt_char) in attempt_chars.iter().enumerate() {
+            let (pos, ch) = (CharPos(pos), attempt_char.ch);
+            match attempt_char.state {
+                CharResult::Exact => {
+                    if *self.exact.entry(pos).or_insert(ch) != ch || self.is_denied(ch, pos) {
+                        self.contradiction = true;
+                    }
+                    *found_counts.entry(ch).or_default() += 1;
+                }
+                CharResult::NotInPosition => {
+                    // a misplaced char after an unsuccessful one can not be produced by inspection
+                    if unsuccessful_chars.contains(&ch) {
+                        self.contradiction = true;
+                    }
+                    self.misplaced.entry(ch).or_default().insert(pos);
+                    self.deny(ch, pos);
+                    *found_counts.entry(ch).or_default() += 1;
+                }
+                CharResult::Unsuccessful => {
+                    self.deny(ch, pos);
+                    unsuccessful_chars.insert(ch);
+                }
+            }
+        }
+        for (&ch, &count) in &found_counts {
+            let min_count = self.min_counts.entry(ch).or_default();
+            *min_count = (*min_count).max(count);
+        }
+        for ch in unsuccessful_chars {
+            let count = found_counts.get(&ch).copied().unwrap_or_default();
+            let max_count = self.max_counts.entry(ch).or_insert(count);
+            *max_count = (*max_count).min(count);
+        }
+        if self
+            .max_counts
+            .iter()
+            .any(|(ch, &max_count)| self.min_count(*ch) > max_count)
+        {
+            self.contradiction = true;
+        }
+    }
+
+    // denying a char where another row confirmed it exact is a contradiction
+    fn deny(&mut self, ch: char, pos: CharPos) {
+        if self.exact.get(&pos) == Some(&ch) {
+            self.contradiction = true;
+        }
+        self.denied_poses.entry(ch).or_default().insert(pos);
+    }
+
+    fn is_denied(&self, ch: char, pos: CharPos) -> bool {
+        matches!(self.denied_poses.get(&ch), Some(poses) if poses.contains(&pos))
+    }
+
+    #[inline]
+    pub fn exact(&self) -> &HashMap<CharPos, char> {
+        &self.exact
+    }
+
+    #[inline]
+    pub fn misplaced(&self) -> &HashMap<char, HashSet<CharPos>> {
+        &self.misplaced
+    }
+
+    #[inline]
+    pub fn denied_poses(&self, ch: char) -> Option<&HashSet<CharPos>> {
+        self.denied_poses.get(&ch)
+    }
+
+    #[inline]
+    pub fn min_count(&self, ch: char) -> usize {
+        self.min_counts.get(&ch).copied().unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn max_count(&self, ch: char) -> Option<usize> {
+        self.max_counts.get(&ch).copied()
+    }
+
+    #[inline]
+    pub fn is_contradiction(&self) -> bool {
+        self.contradiction
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        if self.contradiction {
+            return false;
+        }
+        let chars = word.chars().collect_vec();
+        if matches!(self.word_len, Some(word_len) if word_len != chars.len()) {
+            return false;
+        }
+        let positions_ok = chars.iter().enumerate().all(|(pos, &ch)| {
+            let pos = CharPos(pos);
+            match self.exact.get(&pos) {
+                Some(&exact_ch) => exact_ch == ch,
+                None => !self.is_denied(ch, pos),
+            }
+        });
+        if !positions_ok {
+            return false;
+        }
+        let counts = chars.iter().copied().counts();
+        self.min_counts
+            .iter()
+            .all(|(ch, &min_count)| counts.get(ch).copied().unwrap_or_default() >= min_count)
+            && self
+                .max_counts
+                .iter()
+                .all(|(ch, &max_count)| counts.get(ch).copied().unwrap_or_default() <= max_count)
+    }
+
+    pub fn hard_mode_allows(&self, word: &str) -> bool {
+        let chars = word.chars().collect_vec();
+        let counts = chars.iter().copied().counts();
+        self.exact
+            .iter()
+            .all(|(&CharPos(pos), ch)| chars.get(pos) == Some(ch))
+            && self
+                .min_counts
+                .iter()
+                .all(|(ch, &min_count)| counts.get(ch).copied().unwrap_or_default() >= min_count)
+    }
+
+    pub fn hard_mode_filter(&self, dict: &Dict) -> Dict {
+        let mut dict = dict.clone();
+        for (&pos, &ch) in &self.exact {
+            dict.only_chars_at_poses(&[pos].into(), &[ch].into());
+        }
+        dict.retain(|word| self.hard_mode_allows(word));
+        dict
+    }
+
+    pub fn filter_dict(&self, dict: &Dict) -> Dict {
+        if self.contradiction {
+            return Dict::empty();
+        }
+        let mut dict = dict.clone();
+        for (&pos, &ch) in &self.exact {
+            dict.only_chars_at_poses(&[pos].into(), &[ch].into());
+        }
+        dict.deny_chars(
+            &self
+                .max_counts
+                .iter()
+                .filter(|(_, &max_count)| max_count == 0)
+                .map(|(&ch, _)| ch)
+                .collect(),
+        );
+        dict.retain(|word| self.matches(word));
+        dict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CharPositions;
+
+    fn filter_by_inspection(dict: &Dict, attempts: &[Attempt]) -> Dict {
+        let mut dict = dict.clone();
+        dict.retain(|word| {
+            let char_positions = CharPositions::new(word);
+            attempts
+                .iter()
+                .all(|attempt| &Attempt::evaluate(&attempt.word(), &char_positions) == attempt)
+        });
+        dict
+    }
+
+    #[test]
+    fn observe_counts() {
+        let knowledge = Knowledge::from_attempts(&["а?г а?г а ".parse().unwrap()]);
+        assert_eq!(knowledge.min_count('а'), 2);
+        assert_eq!(knowledge.max_count('а'), Some(2));
+        assert_eq!(knowledge.max_count('г'), Some(0));
+        assert_eq!(
+            knowledge.denied_poses('а'),
+            Some(&[CharPos(0), CharPos(2), CharPos(4)].into())
+        );
+        assert!(!knowledge.is_contradiction());
+    }
+
+    #[test]
+    fn observe_min_count_without_max() {
+        let knowledge = Knowledge::from_attempts(&["н?а+з а н+".parse().unwrap()]);
+        assert_eq!(
+            knowledge.exact(),
+            &[(CharPos(1), 'а'), (CharPos(4), 'н')].into()
+        );
+        assert_eq!(knowledge.misplaced(), &[('н', [CharPos(0)].into())].into());
+        assert_eq!(knowledge.min_count('н'), 2);
+        assert_eq!(knowledge.max_count('н'), None);
+        assert_eq!(knowledge.max_count('а'), Some(1));
+    }
+
+    #[test]
+    fn observe_unsuccessful_before_misplaced() {
+        let knowledge = Knowledge::from_attempts(&["а г а?г о ".parse().unwrap()]);
+        assert!(knowledge.is_contradiction());
+        assert!(!knowledge.matches("бабва"));
+    }
+
+    #[test]
+    fn observe_conflicting_exact() {
+        let knowledge = Knowledge::from_attempts(&[
+            "с+а з а н ".parse().unwrap(),
+            "к+а з а н ".parse().unwrap(),
+        ]);
+        assert!(knowledge.is_contradiction());
+    }
+
+    #[test]
+    fn matches_repeated_letters() {
+        let knowledge = Knowledge::from_attempts(&["а?г а?г о ".parse().unwrap()]);
+        assert!(knowledge.matches("бабва"));
+        assert!(!knowledge.matches("бабвв"));
+        assert!(!knowledge.matches("аабва"));
+        assert!(!knowledge.matches("бабваа"));
+    }
+
+    #[test]
+    fn filter_dict_same_as_inspection() {
+        let dict = Dict::default();
+        for (secret, guesses) in [
+            ("сазан", &["казна", "нотка"][..]),
+            ("парус", &["парад", "аорта"][..]),
+            ("шалаш", &["атака", "шакал"][..]),
+            ("налог", &["каток", "анонс", "слово"][..]),
+        ] {
+            let attempts = guesses
+                .iter()
+                .map(|guess| Attempt::inspect_input(guess, &secret.into(), &dict).unwrap())
+                .collect_vec();
+            let filtered = Knowledge::from_attempts(&attempts).filter_dict(&dict);
+            assert!(filtered.word_in_dict(secret));
+            assert_eq!(filtered, filter_by_inspection(&dict, &attempts));
+        }
+        // no secret produces both rows, whatever their order
+        for rows in [["к?а з н а ", "к+о т а н "], ["к+о т а н ", "к?а з н а "]]
+        {
+            let attempts = rows.map(|row| row.parse::<Attempt>().unwrap());
+            let knowledge = Knowledge::from_attempts(&attempts);
+            assert!(knowledge.is_contradiction());
+            assert_eq!(knowledge.filter_dict(&dict), Dict::empty());
+            assert_eq!(filter_by_inspection(&dict, &attempts), Dict::empty());
+        }
+    }
+
+    #[test]
+    fn hard_mode_allows() {
+        let knowledge = Knowledge::from_attempts(&["к а+з+н?а?".parse().unwrap()]);
+        assert!(knowledge.hard_mode_allows("сазан"));
+        assert!(knowledge.hard_mode_allows("казан"));
+        assert!(knowledge.hard_mode_allows("назад"));
+        assert!(!knowledge.hard_mode_allows("базар"));
+        assert!(!knowledge.hard_mode_allows("нотка"));
+    }
+
+    #[test]
+    fn hard_mode_filter() {
+        let dict = Dict::default();
+        let knowledge = Knowledge::from_attempts(&["к а+з+н?а?".parse().unwrap()]);
+        let allowed = knowledge.hard_mode_filter(&dict);
+        assert!(allowed.word_in_dict("казан"));
+        assert!(!allowed.word_in_dict("базар"));
+        assert!(allowed
+            .words()
+            .iter()
+            .all(|word| knowledge.hard_mode_allows(word)));
+        let mut expected = dict.clone();
+        expected.retain(|word| knowledge.hard_mode_allows(word));
+        assert_eq!(allowed, expected);
+    }
+
+    #[test]
+    fn filter_dict_contradiction() {
+        let dict = Dict::default();
+        let knowledge = Knowledge::from_attempts(&[
+            "с+а з а н ".parse().unwrap(),
+            "к+а з а н ".parse().unwrap(),
+        ]);
+        assert!(knowledge.is_contradiction());
+        assert_eq!(knowledge.filter_dict(&dict), Dict::empty());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    solver::{
+        entropy::{bucket_sizer, bucket_sizes, candidate_chars, cmp_as_answer},
+        PatternMatrix,
+    },
+    Dict,
+};
+use itertools::Itertools;
+
+pub fn worst_case(guess: &str, candidates: &Dict) -> usize {
+    bucket_sizes(guess, &candidate_chars(candidates))
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+}
+
+pub fn rank_by_worst_case(guesses: &Dict, candidates: &Dict) -> Vec<(&'static str, usize)> {
+    rank(guesses, candidates, bucket_sizer(None, candidates))
+}
+
+pub fn rank_by_worst_case_with_matrix(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    candidates: &Dict,
+) -> Vec<(&'static str, usize)> {
+    rank(guesses, candidates, bucket_sizer(Some(matrix), candidates))
+}
+
+fn rank(
+    guesses: &Dict,
+    candidates: &Dict,
+    bucket_sizes: impl Fn(&str) -> Vec<usize>,
+) -> Vec<(&'static str, usize)> {
+    guesses
+        .words()
+        .iter()
+        .map(|&guess| {
+            (
+                guess,
+                bucket_sizes(guess).into_iter().max().unwrap_or_default(),
+            )
+        })
+        .sorted_by(|(a_guess, a_max_bucket), (b_guess, b_max_bucket)| {
+            a_max_bucket
+                .cmp(b_max_bucket)
+                .then_with(|| cmp_as_answer(candidates, a_guess, b_guess))
+                .then_with(|| a_guess.cmp(b_guess))
+        })
+        .collect()
+}
+
+pub fn suggest_word_by_worst_case(guesses: &Dict, candidates: &Dict) -> Option<&'static str> {
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => rank_by_worst_case(guesses, candidates)
+            .first()
+            .map(|&(word, _)| word),
+    }
+}
+
+pub fn suggest_word_by_worst_case_with_matrix(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    candidates: &Dict,
+) -> Option<&'static str> {
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => rank_by_worst_case_with_matrix(matrix, guesses, candidates)
+            .first()
+            .map(|&(word, _)| word),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worst_case() {
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        assert_eq!(super::worst_case("каска", &candidates), 1);
+        assert_eq!(super::worst_case("казан", &candidates), 2);
+        assert_eq!(super::worst_case("сазан", &Dict::empty()), 0);
+    }
+
+    #[test]
+    fn rank_by_worst_case() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let ranked = super::rank_by_worst_case(&dict, &candidates);
+        assert_eq!(ranked.len(), 5);
+        assert!(ranked.iter().tuple_windows().all(|((_, a), (_, b))| a <= b));
+        assert_eq!(ranked.first(), Some(&("каска", 1)));
+    }
+
+    #[test]
+    fn rank_by_worst_case_with_matrix() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        assert_eq!(
+            super::rank_by_worst_case_with_matrix(&matrix, &dict, &candidates),
+            super::rank_by_worst_case(&dict, &candidates)
+        );
+        assert_eq!(
+            super::suggest_word_by_worst_case_with_matrix(&matrix, &dict, &candidates),
+            Some("каска")
+        );
+    }
+
+    #[test]
+    fn rank_by_worst_case_prefers_candidates() {
+        let dict = Dict::from_iter(["казан", "каска", "сазан"]);
+        let candidates = Dict::from_iter(["казан", "сазан"]);
+        assert_eq!(
+            super::rank_by_worst_case(&dict, &candidates),
+            [("казан", 1), ("сазан", 1), ("каска", 1)]
+        );
+    }
+
+    #[test]
+    fn suggest_word_by_worst_case() {
+        let dict = Dict::default();
+        let candidates = Dict::from_iter(["казан", "сазан", "фазан"]);
+        let suggestion = super::suggest_word_by_worst_case(&dict, &candidates).unwrap();
+        assert_eq!(super::worst_case(suggestion, &candidates), 1);
+        assert_eq!(
+            super::suggest_word_by_worst_case(&dict, &Dict::from_iter(["сазан"])),
+            Some("сазан")
+        );
+        assert_eq!(
+            super::suggest_word_by_worst_case(&dict, &Dict::empty()),
+            None
+        );
+    }
+}
@@ -1,4 +1,52 @@
-use crate::{CharPos, Dict};
+mod absurdle;
+mod bench;
+mod difficulty;
+mod entropy;
+mod error;
+mod expected_cost;
+mod fibble;
+pub mod jotto;
+mod knowledge;
+mod minimax;
+mod multi_board;
+mod openers;
+mod optimal;
+mod partition;
+mod pattern_matrix;
+mod strategy;
+mod trap;
+
+pub use absurdle::{absurdle_win, AbsurdleWin};
+pub use bench::{bench, BenchGame, BenchReport};
+pub use difficulty::{WordRating, WordRatings};
+pub use entropy::{
+    entropy, rank_by_entropy, rank_by_entropy_with_matrix, suggest_word_by_entropy,
+    suggest_word_by_entropy_with_matrix,
+};
+pub use error::{PatternCacheError, RatingsError};
+pub use expected_cost::{
+    expected_cost, rank_by_expected_cost, rank_by_expected_cost_with_matrix,
+    suggest_word_by_expected_cost, suggest_word_by_expected_cost_with_matrix,
+};
+pub use fibble::{FibbleSolver, FIBBLE_LIE_PROBABILITY};
+pub use knowledge::Knowledge;
+pub use minimax::{
+    rank_by_worst_case, rank_by_worst_case_with_matrix, suggest_word_by_worst_case,
+    suggest_word_by_worst_case_with_matrix, worst_case,
+};
+pub use multi_board::MultiBoardSolver;
+pub use openers::{rank_openers, Opener, OpenerMetric};
+pub use optimal::{anytime_tree, optimal_tree, DecisionTree, Objective, OptimalTree};
+pub use partition::{partition, Bucket};
+pub use pattern_matrix::{default_cache_dir, PatternMatrix};
+pub use strategy::{
+    auto_play, AnytimeSolver, EntropySolver, ExpectedCostSolver, FrequencySolver, MinimaxSolver,
+    ProbePolicy, Solver, SolverOptions, Strategy, Suggestion,
+};
+pub use trap::{trap_families, TrapFamily};
+
+use crate::{Attempt, CharPos, Dict};
+use itertools::Itertools;
 use num_rational::Ratio;
 use std::{
     cmp::max,
@@ -62,6 +110,10 @@ pub fn find_pos_char_with_max_weight_in_pos(
         .map(|(pos, ch, _, _)| (pos, ch))
 }
 
+pub fn candidates(dict: &Dict, attempts: &[Attempt]) -> Dict {
+    Knowledge::from_attempts(attempts).filter_dict(dict)
+}
+
 #[derive(Clone)]
 struct SuggestWordState {
     positions: HashSet<CharPos>,
@@ -93,6 +145,45 @@ pub fn suggest_word(dict: Dict) -> Option<Vec<char>> {
     }
 }
 
+// letters which tell candidates apart: present in about half of them,
+// at a position and anywhere in the word
+pub fn suggest_probe(guesses: &Dict, candidates: &Dict) -> Option<&'static str> {
+    let total = candidates.words().len();
+    let split = |count: usize| count.min(total - count);
+    let score = |word: &str| {
+        let at_pos = word
+            .chars()
+            .enumerate()
+            .filter_map(|(pos, ch)| candidates.char_at_pos_index().get(&CharPos(pos))?.get(&ch))
+            .map(|word_indices| split(word_indices.len()))
+            .sum::<usize>();
+        let anywhere = word
+            .chars()
+            .unique()
+            .filter_map(|ch| candidates.global_char_index().get(&ch))
+            .map(|word_indices| split(word_indices.len()))
+            .sum::<usize>();
+        at_pos + anywhere
+    };
+    match candidates.words() {
+        [] => None,
+        [word] => Some(word),
+        _ => guesses
+            .words()
+            .iter()
+            .map(|&word| (word, score(word)))
+            .sorted_by(|(a_word, a_score), (b_word, b_score)| {
+                a_score
+                    .cmp(b_score)
+                    .reverse()
+                    .then_with(|| entropy::cmp_as_answer(candidates, a_word, b_word))
+                    .then_with(|| a_word.cmp(b_word))
+            })
+            .next()
+            .map(|(word, _)| word),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -158,6 +249,43 @@ mod tests {
         assert_eq!(suggest_word, Some("щетка".chars().collect()));
     }
 
+    #[test]
+    fn suggest_probe() {
+        let dict = Dict::default();
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let probe = super::suggest_probe(&dict, &candidates).unwrap();
+        assert!(!candidates.word_in_dict(probe));
+        assert_eq!(
+            probe
+                .chars()
+                .filter(|ch| "вкся".contains(*ch))
+                .unique()
+                .count(),
+            3
+        );
+        assert_eq!(
+            super::suggest_probe(&candidates, &candidates)
+                .map(|word| candidates.word_in_dict(word)),
+            Some(true)
+        );
+        assert_eq!(
+            super::suggest_probe(&dict, &Dict::from_iter(["сазан"])),
+            Some("сазан")
+        );
+        assert_eq!(super::suggest_probe(&dict, &Dict::empty()), None);
+    }
+
+    #[test]
+    fn candidates() {
+        let dict = Dict::default();
+        let attempts = [
+            Attempt::inspect_input("казна", &"сазан".into(), &dict).unwrap(),
+            Attempt::inspect_input("фазан", &"сазан".into(), &dict).unwrap(),
+        ];
+        let candidates = super::candidates(&dict, &attempts);
+        assert_eq!(candidates.words(), ["сазан"]);
+    }
+
     #[test]
     fn find_pos_char_with_max_weight_in_pos_empty() {
         let dict = Dict::empty();
@@ -0,0 +1,207 @@
+use crate::{
+    solver::{
+        entropy::{bucket_weigher, entropy_of_weights},
+        Knowledge, PatternCacheError, PatternMatrix,
+    },
+    Attempt, Dict,
+};
+use itertools::Itertools;
+
+struct Board {
+    candidates: Dict,
+    solved: bool,
+}
+
+// every guess is played on all boards, a board is finished once it is guessed
+pub struct MultiBoardSolver<'a> {
+    dict: &'a Dict,
+    matrix: PatternMatrix,
+    boards: Vec<Board>,
+}
+
+impl<'a> MultiBoardSolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict, boards: usize) -> Self {
+        Self::with_matrix_unchecked(dict, boards, PatternMatrix::from_dict(dict))
+    }
+
+    pub fn with_matrix(
+        dict: &'a Dict,
+        boards: usize,
+        matrix: PatternMatrix,
+    ) -> Result<Self, PatternCacheError> {
+        matrix.check_dict(dict)?;
+        Ok(Self::with_matrix_unchecked(dict, boards, matrix))
+    }
+
+    fn with_matrix_unchecked(dict: &'a Dict, boards: usize, matrix: PatternMatrix) -> Self {
+        let mut solver = Self {
+            dict,
+            matrix,
+            boards: Vec::with_capacity(boards),
+        };
+        solver.boards.resize_with(boards, || Board {
+            candidates: Dict::empty(),
+            solved: false,
+        });
+        solver.start();
+        solver
+    }
+
+    pub fn start(&mut self) {
+        for board in &mut self.boards {
+            board.candidates = self.dict.clone();
+            board.solved = false;
+        }
+    }
+
+    #[inline]
+    pub fn boards(&self) -> usize {
+        self.boards.len()
+    }
+
+    #[inline]
+    pub fn candidates(&self, board: usize) -> &Dict {
+        &self.boards[board].candidates
+    }
+
+    #[inline]
+    pub fn is_solved(&self, board: usize) -> bool {
+        self.boards[board].solved
+    }
+
+    pub fn unsolved(&self) -> Vec<usize> {
+        (0..self.boards.len())
+            .filter(|&board| !self.is_solved(board))
+            .collect()
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.boards.iter().all(|board| board.solved)
+    }
+
+    // one attempt per unsolved board, in board order
+    pub fn observe(&mut self, attempts: &[Attempt]) {
+        debug_assert_eq!(attempts.len(), self.unsolved().len());
+        for (board, attempt) in self
+            .boards
+            .iter_mut()
+            .filter(|board| !board.solved)
+            .zip(attempts)
+        {
+            board.candidates = Knowledge::from_attempts([attempt]).filter_dict(&board.candidates);
+            board.solved = attempt.is_win_attempt();
+        }
+    }
+
+    // a board down to its answer is solved for a single guess, otherwise the most information
+    // over all unsolved boards wins, a guess able to solve some of them breaks ties
+    pub fn suggest(&self) -> Option<&'static str> {
+        let unsolved = self
+            .boards
+            .iter()
+            .filter(|board| !board.solved)
+            .collect_vec();
+        if unsolved.is_empty()
+            || unsolved
+                .iter()
+                .any(|board| board.candidates.words().is_empty())
+        {
+            return None;
+        }
+        if let Some(board) = unsolved
+            .iter()
+            .find(|board| board.candidates.words().len() == 1)
+        {
+            return board.candidates.words().first().copied();
+        }
+        let weighers = unsolved
+            .iter()
+            .map(|board| bucket_weigher(Some(&self.matrix), &board.candidates))
+            .collect_vec();
+        self.dict
+            .words()
+            .iter()
+            .map(|&guess| {
+                let entropy = weighers
+                    .iter()
+                    .map(|bucket_weights| entropy_of_weights(&bucket_weights(guess)))
+                    .sum::<f64>();
+                let answer_on = unsolved
+                    .iter()
+                    .filter(|board| board.candidates.word_in_dict(guess))
+                    .count();
+                (guess, entropy, answer_on)
+            })
+            .max_by(
+                |(a_guess, a_entropy, a_answer_on), (b_guess, b_entropy, b_answer_on)| {
+                    a_entropy
+                        .total_cmp(b_entropy)
+                        .then_with(|| a_answer_on.cmp(b_answer_on))
+                        .then_with(|| a_guess.cmp(b_guess).reverse())
+                },
+            )
+            .map(|(guess, _, _)| guess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solver, CharPositions};
+
+    fn attempts(guess: &str, answers: &[&str]) -> Vec<Attempt> {
+        answers
+            .iter()
+            .map(|&answer| Attempt::evaluate(guess, &CharPositions::new(answer)))
+            .collect()
+    }
+
+    #[test]
+    fn observe() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let mut solver = MultiBoardSolver::new(&dict, 2);
+        assert_eq!(solver.boards(), 2);
+        assert_eq!(solver.unsolved(), [0, 1]);
+        solver.observe(&attempts("казан", &["казан", "сазан"]));
+        assert!(solver.is_solved(0));
+        assert!(!solver.is_solved(1));
+        assert_eq!(solver.unsolved(), [1]);
+        assert_eq!(solver.candidates(1).words(), ["сазан", "фазан"]);
+        assert!(!solver.is_finished());
+        solver.observe(&attempts("сазан", &["сазан"]));
+        assert!(solver.is_finished());
+        assert_eq!(solver.suggest(), None);
+        solver.start();
+        assert_eq!(solver.unsolved(), [0, 1]);
+        assert_eq!(solver.candidates(0), &dict);
+    }
+
+    #[test]
+    fn suggest() {
+        let dict = Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"]);
+        let mut solver = MultiBoardSolver::new(&dict, 2);
+        assert_eq!(solver.suggest(), Some("каска"));
+        solver.observe(&attempts("каска", &["вязан", "казан"]));
+        // the first board is down to its answer
+        assert_eq!(solver.candidates(0).words(), ["вязан"]);
+        assert_eq!(solver.suggest(), Some("вязан"));
+        solver.observe(&attempts("вязан", &["вязан", "казан"]));
+        assert_eq!(solver.suggest(), Some("казан"));
+    }
+
+    #[test]
+    fn suggest_combined() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        let mut solver = MultiBoardSolver::new(&dict, 2);
+        solver.observe(&attempts("майка", &["шайка", "лайка"]));
+        let entropy = |word| {
+            solver::entropy(word, solver.candidates(0))
+                + solver::entropy(word, solver.candidates(1))
+        };
+        let best = entropy(solver.suggest().unwrap());
+        assert!(dict.words().iter().all(|word| entropy(word) <= best));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    solver::{
+        entropy::{bucket_sizer, bucket_weigher, entropy_of_weights},
+        pos_stats,
+    },
+    CharPos, Dict,
+};
+use itertools::Itertools;
+use std::iter::zip;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum OpenerMetric {
+    Entropy,
+    ExpectedRemaining,
+    WorstCase,
+    Frequency,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Opener {
+    pub word: &'static str,
+    pub entropy: f64,
+    pub expected_remaining: f64,
+    pub worst_case: usize,
+    pub frequency: usize,
+}
+
+pub fn rank_openers(
+    guesses: &Dict,
+    answers: &Dict,
+    metric: OpenerMetric,
+    distinct_letters: bool,
+) -> Vec<Opener> {
+    let bucket_sizes = bucket_sizer(None, answers);
+    let bucket_weights = bucket_weigher(None, answers);
+    let pos_stats = pos_stats(answers);
+    guesses
+        .words()
+        .iter()
+        .filter(|word| !distinct_letters || word.chars().all_unique())
+        .map(|&word| {
+            let sizes = bucket_sizes(word);
+            let weights = bucket_weights(word);
+            let total = weights.iter().sum::<f64>();
+            Opener {
+                word,
+                entropy: entropy_of_weights(&weights),
+                expected_remaining: if total > 0.0 {
+                    zip(&sizes, &weights)
+                        .map(|(&size, weight)| size as f64 * weight)
+                        .sum::<f64>()
+                        / total
+                } else {
+                    0.0
+                },
+                worst_case: sizes.into_iter().max().unwrap_or_default(),
+                frequency: word
+                    .chars()
+                    .enumerate()
+                    .filter_map(|(pos, ch)| pos_stats.get(&CharPos(pos))?.get(&ch))
+                    .sum(),
+            }
+        })
+        .sorted_by(|a, b| {
+            match metric {
+                OpenerMetric::Entropy => a.entropy.total_cmp(&b.entropy).reverse(),
+                OpenerMetric::ExpectedRemaining => {
+                    a.expected_remaining.total_cmp(&b.expected_remaining)
+                }
+                OpenerMetric::WorstCase => a.worst_case.cmp(&b.worst_case),
+                OpenerMetric::Frequency => a.frequency.cmp(&b.frequency).reverse(),
+            }
+            .then_with(|| a.word.cmp(b.word))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict() -> Dict {
+        Dict::from_iter(["вязан", "казан", "каска", "сазан", "фазан"])
+    }
+
+    fn words(openers: &[Opener]) -> Vec<&'static str> {
+        openers.iter().map(|opener| opener.word).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let dict = dict();
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        let ranked = rank_openers(&dict, &candidates, OpenerMetric::Entropy, false);
+        assert_eq!(
+            ranked[0],
+            Opener {
+                word: "каска",
+                entropy: 2.0,
+                expected_remaining: 1.0,
+                worst_case: 1,
+                frequency: 4,
+            }
+        );
+        let kazan = ranked.iter().find(|opener| opener.word == "казан").unwrap();
+        assert_eq!(kazan.entropy, 1.5);
+        assert_eq!(kazan.expected_remaining, (1 + 1 + 4) as f64 / 4.0);
+        assert_eq!(kazan.worst_case, 2);
+        assert_eq!(kazan.frequency, 1 + 3 + 4 + 4 + 4);
+    }
+
+    #[test]
+    fn metrics() {
+        let dict = dict();
+        let rank = |metric| words(&rank_openers(&dict, &dict, metric, false));
+        assert_eq!(rank(OpenerMetric::Entropy)[0], "каска");
+        assert_eq!(rank(OpenerMetric::ExpectedRemaining)[0], "каска");
+        assert_eq!(rank(OpenerMetric::WorstCase)[0], "каска");
+        assert_eq!(
+            rank(OpenerMetric::Frequency),
+            ["казан", "сазан", "фазан", "вязан", "каска"]
+        );
+    }
+
+    #[test]
+    fn distinct_letters() {
+        let dict = Dict::from_iter(["казан", "каска", "нотка", "парус"]);
+        assert_eq!(
+            words(&rank_openers(&dict, &dict, OpenerMetric::Frequency, true)),
+            ["нотка", "парус"]
+        );
+    }
+}
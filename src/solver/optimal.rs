@@ -0,0 +1,831 @@
+use crate::{solver::PatternMatrix, Attempt, Dict, Pattern};
+use itertools::Itertools;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    iter::zip,
+    time::Instant,
+};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Objective {
+    Expected,
+    WorstCase,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DecisionTree {
+    pub guess: &'static str,
+    pub branches: Vec<(Attempt, DecisionTree)>,
+}
+
+impl DecisionTree {
+    pub fn depth(&self) -> usize {
+        1 + self
+            .branches
+            .iter()
+            .map(|(_, tree)| tree.depth())
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn write_branches(&self, f: &mut Formatter<'_>, indent: usize) -> std::fmt::Result {
+        for (attempt, tree) in &self.branches {
+            writeln!(f, "{:indent$}{attempt} => {}", "", tree.guess)?;
+            tree.write_branches(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DecisionTree {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.guess)?;
+        self.write_branches(f, 2)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OptimalTree {
+    pub tree: DecisionTree,
+    pub answers: usize,
+    pub total_guesses: usize,
+    pub worst_case: usize,
+    pub breadth: Option<usize>,
+    pub proven: bool,
+}
+
+impl OptimalTree {
+    #[inline]
+    pub fn expected(&self) -> f64 {
+        self.total_guesses as f64 / self.answers as f64
+    }
+}
+
+const UNSOLVABLE: usize = usize::MAX / 2;
+const UNLIMITED_DEPTH: usize = usize::MAX;
+
+fn lower_bound(size: usize) -> usize {
+    (2 * size).saturating_sub(1)
+}
+
+fn least_bound(size: usize, guess_bounds: &[(usize, usize)]) -> usize {
+    guess_bounds
+        .iter()
+        .map(|&(bound, _)| bound)
+        .min()
+        .unwrap_or(UNSOLVABLE)
+        .max(lower_bound(size))
+}
+
+fn next_depth(depth: usize) -> usize {
+    if depth == UNLIMITED_DEPTH {
+        depth
+    } else {
+        depth - 1
+    }
+}
+
+enum Outcome {
+    Solved(usize, usize),
+    Bound(usize),
+}
+
+#[derive(Default)]
+struct MemoEntry {
+    // the guess bounds were computed for the set
+    bounded: bool,
+    // the bounds of the buckets of every guess were computed for the set
+    deep_bounded: bool,
+    lower_bound: usize,
+    solved: Option<(usize, usize)>,
+}
+
+// guesses by how well they split a set, as (buckets + win, guess) best first: no guess splits
+// a subset into more buckets than the whole set, or wins on more of it
+struct Split {
+    scores: Vec<(usize, usize)>,
+}
+
+impl Split {
+    fn new(size: usize, guess_bounds: &[(usize, usize)]) -> Self {
+        let mut scores = guess_bounds
+            .iter()
+            .map(|&(bound, guess)| (3 * size - bound, guess))
+            .collect_vec();
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        Self { scores }
+    }
+
+    // the guesses which may score more than that on a subset
+    fn above(&self, score: usize) -> Vec<usize> {
+        self.scores
+            .iter()
+            .take_while(|&&(guess_score, _)| guess_score > score)
+            .map(|&(_, guess)| guess)
+            .collect()
+    }
+
+    // a subset with the bounds of the first guesses computed, the others keep their scores
+    fn narrow(&self, size: usize, guess_bounds: &[(usize, usize)], computed: usize) -> Self {
+        let mut split = Self::new(size, guess_bounds);
+        split.scores.extend_from_slice(&self.scores[computed..]);
+        split.scores.sort_unstable_by(|a, b| b.cmp(a));
+        split
+    }
+}
+
+struct Search<'a> {
+    matrix: &'a PatternMatrix,
+    pool: &'a [usize],
+    answer_guesses: &'a [usize],
+    win_pattern: Pattern,
+    breadth: Option<usize>,
+    deadline: Option<Instant>,
+    expired: bool,
+    // a bit per guess and pattern, words of the bitset per guess
+    seen_patterns: Vec<u64>,
+    pattern_words: usize,
+    bucket_counts: Vec<usize>,
+    is_answer: Vec<bool>,
+    memo: HashMap<(Vec<u16>, usize), MemoEntry>,
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        matrix: &'a PatternMatrix,
+        pool: &'a [usize],
+        answer_guesses: &'a [usize],
+        breadth: Option<usize>,
+        deadline: Option<Instant>,
+    ) -> Self {
+        let guesses = matrix.guesses().len();
+        let pattern_words = (Pattern::count(matrix.word_len()) + 63) / 64;
+        Self {
+            matrix,
+            pool,
+            answer_guesses,
+            win_pattern: Pattern::win(matrix.word_len()),
+            breadth,
+            deadline,
+            expired: false,
+            seen_patterns: vec![0; guesses * pattern_words],
+            pattern_words,
+            bucket_counts: vec![0; guesses],
+            is_answer: vec![false; guesses],
+            memo: HashMap::new(),
+        }
+    }
+
+    fn expired(&mut self) -> bool {
+        if !self.expired {
+            self.expired = self
+                .deadline
+                .map_or(false, |deadline| Instant::now() >= deadline);
+        }
+        self.expired
+    }
+
+    fn buckets(&self, guess: usize, answers: &[u16]) -> Vec<(Pattern, Vec<u16>)> {
+        answers
+            .iter()
+            .map(|&answer| (self.matrix.get(guess, answer as usize), answer))
+            .into_group_map()
+            .into_iter()
+            .sorted_unstable_by_key(|(pattern, bucket)| (usize::MAX - bucket.len(), *pattern))
+            .collect()
+    }
+
+    fn guess_buckets(&self, guess: usize, answers: &[u16]) -> Vec<Vec<u16>> {
+        self.buckets(guess, answers)
+            .into_iter()
+            .filter(|(pattern, _)| *pattern != self.win_pattern)
+            .map(|(_, bucket)| bucket)
+            .collect()
+    }
+
+    // every answer costs the guess itself, and every answer sharing a bucket with another one
+    // costs at least one more guess; the bounds of all guesses at once walk the matrix by rows
+    fn guess_bounds(
+        &mut self,
+        answers: &[u16],
+        depth: usize,
+        pool: &[usize],
+    ) -> Vec<(usize, usize)> {
+        let pattern_words = self.pattern_words;
+        let (seen_patterns, bucket_counts) = (&mut self.seen_patterns, &mut self.bucket_counts);
+        // branchless, whether a pattern is new is hardly predictable
+        let mark = |pattern: Pattern, seen: &mut [u64], bucket_count: &mut usize| {
+            let code = pattern.index();
+            let bit = 1 << (code % 64);
+            *bucket_count += usize::from(seen[code / 64] & bit == 0);
+            seen[code / 64] |= bit;
+        };
+        let whole_pool = pool.len() == bucket_counts.len();
+        for &answer in answers {
+            let row = self.matrix.answer_row(answer as usize);
+            if whole_pool {
+                for ((&pattern, seen), bucket_count) in zip(
+                    zip(row, seen_patterns.chunks_exact_mut(pattern_words)),
+                    bucket_counts.iter_mut(),
+                ) {
+                    mark(pattern, seen, bucket_count);
+                }
+            } else {
+                for &guess in pool {
+                    let seen = &mut seen_patterns[guess * pattern_words..][..pattern_words];
+                    mark(row[guess], seen, &mut bucket_counts[guess]);
+                }
+            }
+        }
+        for &answer in answers {
+            self.is_answer[self.answer_guesses[answer as usize]] = true;
+        }
+        let bounds = pool
+            .iter()
+            .filter_map(|&guess| {
+                let buckets = self.bucket_counts[guess];
+                let win = usize::from(self.is_answer[guess]);
+                let useless = buckets == 1 && win == 0;
+                let too_deep = depth == 2 && buckets < answers.len();
+                (!useless && !too_deep).then_some((3 * answers.len() - buckets - win, guess))
+            })
+            .collect();
+        // clearing the bitsets whole is cheaper unless there are many more patterns than answers
+        if pattern_words <= answers.len() {
+            for &guess in pool {
+                self.seen_patterns[guess * pattern_words..][..pattern_words].fill(0);
+            }
+        } else {
+            for &answer in answers {
+                let row = self.matrix.answer_row(answer as usize);
+                for &guess in pool {
+                    self.seen_patterns[guess * pattern_words + row[guess].index() / 64] = 0;
+                }
+            }
+        }
+        for &answer in answers {
+            self.is_answer[self.answer_guesses[answer as usize]] = false;
+        }
+        for &guess in pool {
+            self.bucket_counts[guess] = 0;
+        }
+        bounds
+    }
+
+    fn splits_apart(&mut self, answers: &[u16]) -> bool {
+        answers.iter().any(|&guess_answer| {
+            let guess = self.answer_guesses[guess_answer as usize];
+            let seen = &mut self.seen_patterns[..self.pattern_words];
+            let mut apart = true;
+            for &answer in answers {
+                let code = self.matrix.get(guess, answer as usize).index();
+                let bit = 1 << (code % 64);
+                if seen[code / 64] & bit != 0 {
+                    apart = false;
+                    break;
+                }
+                seen[code / 64] |= bit;
+            }
+            seen.fill(0);
+            apart
+        })
+    }
+
+    // the least total any guess may lead to, or just beta once that's the least; the guesses
+    // splitting the set it comes from too poorly to bring the bound below beta are skipped
+    fn set_bound(&mut self, answers: &[u16], depth: usize, beta: usize, split: &Split) -> usize {
+        match (answers, depth) {
+            ([], _) => return 0,
+            (_, 0) => return UNSOLVABLE,
+            ([_], _) => return 1,
+            (_, 1) => return UNSOLVABLE,
+            ([_, _], _) => return 3,
+            _ => {}
+        }
+        let key = (answers.to_vec(), depth);
+        if let Some(entry) = self.memo.get(&key) {
+            if entry.bounded || entry.lower_bound >= beta {
+                return entry.lower_bound;
+            }
+        }
+        // no guess does better than an answer telling all the others apart
+        let (bound, bounded) = if self.splits_apart(answers) {
+            (lower_bound(answers.len()), true)
+        } else {
+            let skipped_score = (3 * answers.len()).saturating_sub(beta);
+            let pool = split.above(skipped_score);
+            let guess_bounds = self.guess_bounds(answers, depth, &pool);
+            let bound = least_bound(answers.len(), &guess_bounds);
+            if bound < beta || pool.len() == split.scores.len() {
+                (bound, true)
+            } else {
+                (beta, false)
+            }
+        };
+        let entry = self.memo.entry(key).or_default();
+        entry.bounded |= bounded;
+        entry.lower_bound = entry.lower_bound.max(bound);
+        entry.lower_bound
+    }
+
+    fn solve(&mut self, answers: &[u16], depth: usize, beta: usize) -> Outcome {
+        match (answers, depth) {
+            ([], _) => return Outcome::Solved(0, 0),
+            (_, 0) => return Outcome::Bound(UNSOLVABLE),
+            ([answer], _) => return Outcome::Solved(1, self.answer_guesses[*answer as usize]),
+            (_, 1) => return Outcome::Bound(UNSOLVABLE),
+            ([answer, _], _) => return Outcome::Solved(3, self.answer_guesses[*answer as usize]),
+            _ => {}
+        }
+        let key = (answers.to_vec(), depth);
+        let entry = self.memo.entry(key.clone()).or_default();
+        if let Some((total, guess)) = entry.solved {
+            return if total < beta {
+                Outcome::Solved(total, guess)
+            } else {
+                Outcome::Bound(total)
+            };
+        }
+        let known_bound = entry.lower_bound.max(lower_bound(answers.len()));
+        if known_bound >= beta {
+            return Outcome::Bound(known_bound);
+        }
+        let outcome = self.solve_uncached(answers, depth, beta);
+        let entry = self.memo.entry(key).or_default();
+        match outcome {
+            Outcome::Solved(total, guess) => {
+                entry.lower_bound = total;
+                entry.solved = Some((total, guess));
+            }
+            Outcome::Bound(bound) => entry.lower_bound = entry.lower_bound.max(bound),
+        }
+        outcome
+    }
+
+    fn solve_uncached(&mut self, answers: &[u16], depth: usize, beta: usize) -> Outcome {
+        if self.expired() {
+            return Outcome::Bound(UNSOLVABLE);
+        }
+        let guess_bounds = self.guess_bounds(answers, depth, self.pool);
+        let set_bound = least_bound(answers.len(), &guess_bounds);
+        {
+            let entry = self.memo.entry((answers.to_vec(), depth)).or_default();
+            entry.bounded = true;
+            entry.lower_bound = entry.lower_bound.max(set_bound);
+        }
+        if set_bound >= beta {
+            return Outcome::Bound(set_bound);
+        }
+        let split = Split::new(answers.len(), &guess_bounds);
+        let mut best = beta;
+        let mut best_guess = None;
+        let mut failed_bound = UNSOLVABLE;
+        for guesses in self.ordered_guesses(answers, guess_bounds) {
+            for (guess_bound, guess) in guesses {
+                if self.expired() {
+                    return Outcome::Bound(UNSOLVABLE);
+                }
+                if guess_bound >= best {
+                    failed_bound = failed_bound.min(guess_bound);
+                    break;
+                }
+                let total = self.solve_guess(answers, depth, guess, best, &split);
+                if total < best {
+                    best = total;
+                    best_guess = Some(guess);
+                } else {
+                    failed_bound = failed_bound.min(total);
+                }
+            }
+        }
+        match best_guess {
+            Some(guess) => Outcome::Solved(best, guess),
+            None => Outcome::Bound(failed_bound),
+        }
+    }
+
+    // answers first, so a guess which may win is preferred on ties
+    fn ordered_guesses(
+        &self,
+        answers: &[u16],
+        guess_bounds: Vec<(usize, usize)>,
+    ) -> [Vec<(usize, usize)>; 2] {
+        let (mut guesses, mut probes): (Vec<_>, Vec<_>) = guess_bounds
+            .into_iter()
+            .partition(|&(_, guess)| self.is_answer_guess(answers, guess));
+        guesses.sort_unstable();
+        probes.sort_unstable();
+        if let Some(breadth) = self.breadth {
+            guesses.truncate(breadth);
+            probes.truncate(breadth);
+        }
+        [guesses, probes]
+    }
+
+    fn is_answer_guess(&self, answers: &[u16], guess: usize) -> bool {
+        answers
+            .iter()
+            .any(|&answer| self.answer_guesses[answer as usize] == guess)
+    }
+
+    // the bounds of the buckets before solving any of them, the largest ones first;
+    // their sum instead once it reaches beta
+    fn bucket_bounds(
+        &mut self,
+        buckets: &[Vec<u16>],
+        depth: usize,
+        beta: usize,
+        split: &Split,
+    ) -> Result<Vec<usize>, usize> {
+        let mut rest_bound = buckets
+            .iter()
+            .map(|bucket| lower_bound(bucket.len()))
+            .sum::<usize>();
+        let mut bounds = Vec::with_capacity(buckets.len());
+        for bucket in buckets {
+            let others = rest_bound - lower_bound(bucket.len());
+            let bound = self.set_bound(bucket, next_depth(depth), beta - others, split);
+            rest_bound = (others + bound).min(UNSOLVABLE);
+            if rest_bound >= beta {
+                return Err(rest_bound);
+            }
+            bounds.push(bound);
+        }
+        Ok(bounds)
+    }
+
+    // the least total any guess may lead to by the bounds of its buckets, or just beta
+    fn deep_bound(&mut self, answers: &[u16], depth: usize, beta: usize, split: &Split) -> usize {
+        let bound = self.set_bound(answers, depth, beta, split);
+        if answers.len() <= 2 || bound >= beta || self.splits_apart(answers) || self.expired() {
+            return bound;
+        }
+        let key = (answers.to_vec(), depth);
+        if let Some(entry) = self.memo.get(&key) {
+            if entry.deep_bounded || entry.solved.is_some() || entry.lower_bound >= beta {
+                return entry.lower_bound.max(bound);
+            }
+        }
+        let pool = split.above((3 * answers.len()).saturating_sub(beta));
+        let guess_bounds = self.guess_bounds(answers, depth, &pool);
+        let bucket_split = split.narrow(answers.len(), &guess_bounds, pool.len());
+        let mut least = beta;
+        for guesses in self.ordered_guesses(answers, guess_bounds) {
+            for (guess_bound, guess) in guesses {
+                if guess_bound >= least {
+                    break;
+                }
+                let buckets = self.guess_buckets(guess, answers);
+                let beta = least - answers.len();
+                if let Ok(bounds) = self.bucket_bounds(&buckets, depth, beta, &bucket_split) {
+                    least = answers.len() + bounds.iter().sum::<usize>();
+                }
+            }
+        }
+        let entry = self.memo.entry(key).or_default();
+        // the least total found below beta is the bound itself, not just a cut
+        entry.deep_bounded = least < beta;
+        entry.lower_bound = entry.lower_bound.max(least);
+        entry.lower_bound
+    }
+
+    fn solve_guess(
+        &mut self,
+        answers: &[u16],
+        depth: usize,
+        guess: usize,
+        beta: usize,
+        split: &Split,
+    ) -> usize {
+        let buckets = self.guess_buckets(guess, answers);
+        let mut total = answers.len();
+        let mut bounds = match self.bucket_bounds(&buckets, depth, beta - total, split) {
+            Ok(bounds) => bounds,
+            Err(bound) => return UNSOLVABLE.min(total + bound),
+        };
+        let mut rest_bound = bounds.iter().sum::<usize>();
+        // a guess doomed by the second ply of its bucket bounds fails before solving any of them;
+        // proving a bucket worth its share of the missing total is cheaper than its exact bound
+        for shared in [true, false] {
+            let mut rest_size = buckets.iter().map(Vec::len).sum::<usize>();
+            for (bucket, bound) in zip(&buckets, &mut bounds) {
+                let others = rest_bound - *bound;
+                let mut cap = beta - total - others;
+                if shared {
+                    let missing = beta - total - rest_bound;
+                    cap = cap.min(*bound + missing.saturating_mul(bucket.len()) / rest_size + 1);
+                    rest_size -= bucket.len();
+                }
+                *bound = self.deep_bound(bucket, next_depth(depth), cap, split);
+                rest_bound = (others + *bound).min(UNSOLVABLE);
+                if total + rest_bound >= beta {
+                    return UNSOLVABLE.min(total + rest_bound);
+                }
+            }
+        }
+        for (bucket, bound) in zip(&buckets, bounds) {
+            rest_bound -= bound;
+            match self.solve(bucket, next_depth(depth), beta - total - rest_bound) {
+                Outcome::Solved(bucket_total, _) => total += bucket_total,
+                Outcome::Bound(bucket_bound) => {
+                    return UNSOLVABLE.min(total + bucket_bound + rest_bound);
+                }
+            }
+        }
+        total
+    }
+
+    fn tree(&mut self, answers: &[u16], depth: usize) -> Option<DecisionTree> {
+        let Outcome::Solved(_, guess) = self.solve(answers, depth, UNSOLVABLE) else {
+            return None;
+        };
+        let word = self.matrix.guesses()[guess];
+        let win_pattern = self.win_pattern;
+        let branches = self
+            .buckets(guess, answers)
+            .into_iter()
+            .filter(|(pattern, _)| *pattern != win_pattern)
+            .map(|(pattern, bucket)| {
+                Some((
+                    pattern.to_attempt(word),
+                    self.tree(&bucket, next_depth(depth))?,
+                ))
+            })
+            .collect::<Option<_>>()?;
+        Some(DecisionTree {
+            guess: word,
+            branches,
+        })
+    }
+
+    fn run(&mut self, answers: &[u16], objective: Objective) -> Option<OptimalTree> {
+        let depth = match objective {
+            Objective::Expected => UNLIMITED_DEPTH,
+            Objective::WorstCase => (1..=answers.len()).find(|&depth| {
+                matches!(self.solve(answers, depth, UNSOLVABLE), Outcome::Solved(..))
+                    || self.expired()
+            })?,
+        };
+        let Outcome::Solved(total_guesses, _) = self.solve(answers, depth, UNSOLVABLE) else {
+            return None;
+        };
+        let tree = self.tree(answers, depth)?;
+        // a search cut short may have missed better guesses and left wrong bounds
+        if self.expired() {
+            return None;
+        }
+        Some(OptimalTree {
+            worst_case: tree.depth(),
+            tree,
+            answers: answers.len(),
+            total_guesses,
+            breadth: self.breadth,
+            proven: self.breadth.is_none(),
+        })
+    }
+}
+
+// none for no answers, or for more of them than the u16 indices of the search hold
+pub fn optimal_tree(
+    guesses: &Dict,
+    answers: &Dict,
+    objective: Objective,
+    breadth: Option<usize>,
+) -> Option<OptimalTree> {
+    answers.words().first()?;
+    let answer_count = u16::try_from(answers.words().len()).ok()?;
+    let guess_words = guesses
+        .words()
+        .iter()
+        .chain(answers.words())
+        .copied()
+        .unique()
+        .collect_vec();
+    let matrix = PatternMatrix::new(&guess_words, answers.words());
+    let pool = (0..guess_words.len()).collect_vec();
+    let answer_guesses = answer_guesses(&matrix)?;
+    let all_answers = (0..answer_count).collect_vec();
+    Search::new(&matrix, &pool, &answer_guesses, breadth, None).run(&all_answers, objective)
+}
+
+fn answer_guesses(matrix: &PatternMatrix) -> Option<Vec<usize>> {
+    matrix
+        .answers()
+        .iter()
+        .map(|answer| matrix.guess_index(answer))
+        .collect()
+}
+
+// progressive refinement: a single most promising guess at every node first,
+// then twice as many each round, until the exact search or the deadline
+pub fn anytime_tree(
+    matrix: &PatternMatrix,
+    guesses: &Dict,
+    answers: &Dict,
+    objective: Objective,
+    deadline: Instant,
+) -> Option<OptimalTree> {
+    answers.words().first()?;
+    let answer_guesses = answer_guesses(matrix)?;
+    let pool = guesses
+        .words()
+        .iter()
+        .chain(answers.words())
+        .filter_map(|word| matrix.guess_index(word))
+        .unique()
+        .collect_vec();
+    let answer_indices = answers
+        .words()
+        .iter()
+        .map(|word| u16::try_from(matrix.answer_index(word)?).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let mut best: Option<OptimalTree> = None;
+    let mut breadth = Some(1);
+    while let Some(tree) = Search::new(matrix, &pool, &answer_guesses, breadth, Some(deadline))
+        .run(&answer_indices, objective)
+    {
+        let key = |tree: &OptimalTree| match objective {
+            Objective::Expected => (tree.total_guesses, 0),
+            Objective::WorstCase => (tree.worst_case, tree.total_guesses),
+        };
+        // the exact search can't be beaten, but may tie with a limited one
+        if best.as_ref().map_or(true, |best| key(&tree) < key(best)) || tree.proven {
+            best = Some(tree);
+        }
+        breadth = match breadth {
+            Some(breadth) if 2 * breadth < pool.len() => Some(2 * breadth),
+            Some(_) => None,
+            None => break,
+        };
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn brute_force(answers: &[&'static str], guesses: &[&'static str]) -> usize {
+        match answers {
+            [] => 0,
+            [_] => 1,
+            _ => guesses
+                .iter()
+                .filter_map(|&guess| {
+                    let buckets = answers
+                        .iter()
+                        .map(|&answer| (Attempt::evaluate(guess, &answer.into()), answer))
+                        .into_group_map();
+                    if buckets.len() == 1 && !answers.contains(&guess) {
+                        return None;
+                    }
+                    Some(
+                        answers.len()
+                            + buckets
+                                .into_iter()
+                                .filter(|(attempt, _)| !attempt.is_win_attempt())
+                                .map(|(_, bucket)| brute_force(&bucket, guesses))
+                                .sum::<usize>(),
+                    )
+                })
+                .min()
+                .unwrap_or(usize::MAX / 2),
+        }
+    }
+
+    #[test]
+    fn optimal_tree_single() {
+        let dict = Dict::from_iter(["сазан"]);
+        let tree = optimal_tree(&dict, &dict, Objective::Expected, None).unwrap();
+        assert_eq!(tree.total_guesses, 1);
+        assert_eq!(tree.worst_case, 1);
+        assert_eq!(
+            tree.tree,
+            DecisionTree {
+                guess: "сазан",
+                branches: vec![]
+            }
+        );
+        assert!(optimal_tree(&dict, &Dict::empty(), Objective::Expected, None).is_none());
+    }
+
+    #[test]
+    fn optimal_tree_probe() {
+        let answers = Dict::from_iter(["байка", "гайка", "зайка", "лайка", "майка"]);
+        let tree = optimal_tree(&Dict::default(), &answers, Objective::Expected, None).unwrap();
+        assert!(!answers.word_in_dict(tree.tree.guess));
+        assert_eq!(tree.total_guesses, 11);
+        assert_eq!(tree.worst_case, 3);
+        assert_eq!(tree.expected(), 2.2);
+        let tree = optimal_tree(&answers, &answers, Objective::Expected, None).unwrap();
+        assert_eq!(tree.total_guesses, 15);
+        assert_eq!(tree.worst_case, 5);
+    }
+
+    #[test]
+    fn optimal_tree_same_as_brute_force() {
+        let dict = Dict::default();
+        let mut answers = dict.clone();
+        answers.retain(|word| word.starts_with("бар"));
+        let optimal = optimal_tree(&answers, &answers, Objective::Expected, None).unwrap();
+        assert!(optimal.proven);
+        assert_eq!(
+            optimal.total_guesses,
+            brute_force(answers.words(), answers.words())
+        );
+    }
+
+    #[test]
+    fn split() {
+        let split = Split::new(4, &[(9, 0), (7, 1), (10, 2)]);
+        assert_eq!(split.scores, [(5, 1), (3, 0), (2, 2)]);
+        assert_eq!(split.above(2), [1, 0]);
+        // the first two guesses split the subset worse, the last one keeps its score on the set
+        let narrowed = split.narrow(3, &[(6, 1), (8, 0)], 2);
+        assert_eq!(narrowed.scores, [(3, 1), (2, 2), (1, 0)]);
+    }
+
+    #[test]
+    fn optimal_tree_worst_case() {
+        let dict = Dict::default();
+        let mut answers = dict.clone();
+        answers.retain(|word| word.starts_with("ба"));
+        let expected = optimal_tree(&answers, &answers, Objective::Expected, None).unwrap();
+        let worst_case = optimal_tree(&answers, &answers, Objective::WorstCase, None).unwrap();
+        assert!(worst_case.worst_case <= expected.worst_case);
+        assert!(worst_case.total_guesses >= expected.total_guesses);
+    }
+
+    #[test]
+    fn anytime_tree() {
+        let dict = Dict::default();
+        let mut answers = dict.clone();
+        answers.retain(|word| word.starts_with("бар"));
+        let matrix = PatternMatrix::from_dict(&answers);
+        let deadline = Instant::now() + Duration::from_secs(3600);
+        let anytime =
+            super::anytime_tree(&matrix, &answers, &answers, Objective::Expected, deadline)
+                .unwrap();
+        let optimal = optimal_tree(&answers, &answers, Objective::Expected, None).unwrap();
+        assert!(anytime.proven);
+        assert_eq!(anytime.breadth, None);
+        assert_eq!(anytime.total_guesses, optimal.total_guesses);
+        assert_eq!(anytime.answers, answers.words().len());
+    }
+
+    #[test]
+    fn anytime_tree_candidates() {
+        let dict = Dict::from_iter(["байка", "гайка", "зайка", "лайка", "майка", "шалаш"]);
+        let answers = Dict::from_iter(["байка", "гайка", "зайка", "лайка", "майка"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        let deadline = Instant::now() + Duration::from_secs(3600);
+        let anytime =
+            super::anytime_tree(&matrix, &dict, &answers, Objective::WorstCase, deadline).unwrap();
+        let optimal = optimal_tree(&dict, &answers, Objective::WorstCase, None).unwrap();
+        assert!(anytime.proven);
+        assert_eq!(anytime.worst_case, optimal.worst_case);
+        assert_eq!(anytime.total_guesses, optimal.total_guesses);
+        assert_eq!(
+            super::anytime_tree(
+                &matrix,
+                &dict,
+                &Dict::empty(),
+                Objective::Expected,
+                deadline
+            )
+            .map(|tree| tree.total_guesses),
+            None
+        );
+    }
+
+    #[test]
+    fn anytime_tree_expired() {
+        let dict = Dict::default();
+        let matrix = PatternMatrix::from_dict(&Dict::from_iter(["казан", "сазан", "фазан"]));
+        let answers = Dict::from_iter(["казан", "сазан", "фазан"]);
+        assert!(super::anytime_tree(
+            &matrix,
+            &dict,
+            &answers,
+            Objective::Expected,
+            Instant::now()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn display_tree() {
+        let dict = Dict::from_iter(["казан", "сазан", "фазан"]);
+        let tree = optimal_tree(&dict, &dict, Objective::Expected, None).unwrap();
+        assert_eq!(
+            tree.tree.to_string(),
+            "\
+            казан\n  \
+              к а+з+а+н+ => сазан\n    \
+                с а+з+а+н+ => фазан\n\
+            "
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Attempt, Dict, Pattern};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Bucket {
+    pub attempt: Attempt,
+    pub words: Vec<&'static str>,
+}
+
+// largest buckets first, equal ones by pattern with the win last
+pub fn partition(guess: &str, candidates: &Dict) -> Vec<Bucket> {
+    let guess_chars = guess.chars().collect_vec();
+    let mut buckets = HashMap::<_, Vec<_>>::new();
+    for &word in candidates.words() {
+        let pattern = Pattern::evaluate(&guess_chars, &word.chars().collect_vec());
+        buckets.entry(pattern).or_default().push(word);
+    }
+    buckets
+        .into_iter()
+        .sorted_by(|(a_pattern, a_words), (b_pattern, b_words)| {
+            a_words
+                .len()
+                .cmp(&b_words.len())
+                .reverse()
+                .then_with(|| a_pattern.cmp(b_pattern))
+        })
+        .map(|(pattern, words)| Bucket {
+            attempt: pattern.to_attempt(guess),
+            words,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver;
+
+    #[test]
+    fn partition() {
+        let candidates = Dict::from_iter(["вязан", "казан", "сазан", "фазан"]);
+        assert_eq!(
+            super::partition("казан", &candidates),
+            [
+                Bucket {
+                    attempt: "к а+з+а+н+".parse().unwrap(),
+                    words: vec!["сазан", "фазан"],
+                },
+                Bucket {
+                    attempt: "к а з+а+н+".parse().unwrap(),
+                    words: vec!["вязан"],
+                },
+                Bucket {
+                    attempt: "к+а+з+а+н+".parse().unwrap(),
+                    words: vec!["казан"],
+                },
+            ]
+        );
+        assert_eq!(super::partition("казан", &Dict::empty()), []);
+    }
+
+    #[test]
+    fn partition_after_history() {
+        let dict = Dict::default();
+        let attempts = ["к а+з+н?а?".parse().unwrap()];
+        let candidates = solver::candidates(&dict, &attempts);
+        let buckets = super::partition("фасон", &candidates);
+        assert_eq!(
+            buckets
+                .iter()
+                .map(|bucket| bucket.words.len())
+                .sum::<usize>(),
+            candidates.words().len()
+        );
+        for bucket in buckets {
+            for word in bucket.words {
+                assert_eq!(Attempt::evaluate("фасон", &word.into()), bucket.attempt);
+            }
+        }
+    }
+}
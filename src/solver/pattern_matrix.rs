@@ -0,0 +1,381 @@
+use crate::{solver::PatternCacheError, Dict, Pattern};
+use itertools::Itertools;
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, BufReader, BufWriter, Read, Write},
+    iter::zip,
+    path::{Path, PathBuf},
+};
+
+const CACHE_MAGIC: &[u8; 8] = b"MORDLEPM";
+const CACHE_VERSION: u32 = 1;
+
+// FNV-1a, stable across builds and platforms unlike the std hashers
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub fn default_cache_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .map(|dir| dir.join("mordle"))
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PatternMatrix {
+    guesses: Vec<&'static str>,
+    answers: Vec<&'static str>,
+    guess_indices: HashMap<&'static str, usize>,
+    answer_indices: HashMap<&'static str, usize>,
+    word_len: usize,
+    patterns: Vec<Pattern>,
+}
+
+impl PatternMatrix {
+    pub fn new(guesses: &[&'static str], answers: &[&'static str]) -> Self {
+        let guess_chars = guesses
+            .iter()
+            .map(|guess| guess.chars().collect_vec())
+            .collect_vec();
+        // answer-major, so patterns of a candidate set for consecutive guesses are close
+        let patterns = answers
+            .iter()
+            .flat_map(|answer| {
+                let answer_chars = answer.chars().collect_vec();
+                guess_chars
+                    .iter()
+                    .map(move |guess_chars| Pattern::evaluate(guess_chars, &answer_chars))
+            })
+            .collect();
+        Self {
+            guesses: guesses.to_vec(),
+            answers: answers.to_vec(),
+            guess_indices: guesses.iter().enumerate().map(|(i, &w)| (w, i)).collect(),
+            answer_indices: answers.iter().enumerate().map(|(i, &w)| (w, i)).collect(),
+            word_len: answers
+                .first()
+                .or(guesses.first())
+                .map_or(0, |word| word.chars().count()),
+            patterns,
+        }
+    }
+
+    #[inline]
+    pub fn from_dict(dict: &Dict) -> Self {
+        Self::new(dict.words(), dict.words())
+    }
+
+    #[inline]
+    pub fn guesses(&self) -> &[&'static str] {
+        &self.guesses
+    }
+
+    #[inline]
+    pub fn answers(&self) -> &[&'static str] {
+        &self.answers
+    }
+
+    #[inline]
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+
+    #[inline]
+    pub fn guess_index(&self, word: &str) -> Option<usize> {
+        self.guess_indices.get(word).copied()
+    }
+
+    #[inline]
+    pub fn answer_index(&self, word: &str) -> Option<usize> {
+        self.answer_indices.get(word).copied()
+    }
+
+    #[inline]
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern {
+        self.patterns[answer * self.guesses.len() + guess]
+    }
+
+    // solvers index the matrix by the words of their dict
+    pub fn check_dict(&self, dict: &Dict) -> Result<(), PatternCacheError> {
+        if self.guesses == dict.words() && self.answers == dict.words() {
+            Ok(())
+        } else {
+            Err(PatternCacheError::DictMismatch)
+        }
+    }
+
+    pub fn fingerprint(guesses: &[&str], answers: &[&str]) -> u64 {
+        let word_len = answers
+            .first()
+            .or(guesses.first())
+            .map_or(0, |word| word.chars().count());
+        let mut hash = fnv1a(0xcbf2_9ce4_8422_2325, &(word_len as u64).to_le_bytes());
+        for words in [guesses, answers] {
+            hash = fnv1a(hash, &(words.len() as u64).to_le_bytes());
+            for word in words {
+                hash = fnv1a(fnv1a(hash, word.as_bytes()), &[0]);
+            }
+        }
+        hash
+    }
+
+    pub fn save(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(CACHE_MAGIC)?;
+        w.write_all(&CACHE_VERSION.to_le_bytes())?;
+        w.write_all(&Self::fingerprint(&self.guesses, &self.answers).to_le_bytes())?;
+        for Pattern(code) in &self.patterns {
+            w.write_all(&code.to_le_bytes())?;
+        }
+        w.flush()
+    }
+
+    pub fn load(
+        r: &mut impl Read,
+        guesses: &[&'static str],
+        answers: &[&'static str],
+    ) -> Result<Self, PatternCacheError> {
+        let mut header = [0; 20];
+        r.read_exact(&mut header)?;
+        let (magic, rest) = header.split_at(CACHE_MAGIC.len());
+        let (version, fingerprint) = rest.split_at(4);
+        if magic != CACHE_MAGIC || version != CACHE_VERSION.to_le_bytes() {
+            return Err(PatternCacheError::BadHeader);
+        }
+        if fingerprint != Self::fingerprint(guesses, answers).to_le_bytes() {
+            return Err(PatternCacheError::StaleDict);
+        }
+        let mut matrix = Self::new(&[], &[]);
+        matrix.guesses = guesses.to_vec();
+        matrix.answers = answers.to_vec();
+        matrix.guess_indices = guesses.iter().enumerate().map(|(i, &w)| (w, i)).collect();
+        matrix.answer_indices = answers.iter().enumerate().map(|(i, &w)| (w, i)).collect();
+        matrix.word_len = answers
+            .first()
+            .or(guesses.first())
+            .map_or(0, |word| word.chars().count());
+        let mut bytes = vec![0; 2 * guesses.len() * answers.len()];
+        r.read_exact(&mut bytes)?;
+        if r.read(&mut [0])? != 0 {
+            return Err(PatternCacheError::BadHeader);
+        }
+        let count = Pattern::count(matrix.word_len);
+        matrix.patterns = bytes
+            .chunks_exact(2)
+            .map(|code| {
+                let code = u16::from_le_bytes([code[0], code[1]]);
+                if usize::from(code) < count {
+                    Ok(Pattern(code))
+                } else {
+                    Err(PatternCacheError::InvalidPattern(code))
+                }
+            })
+            .try_collect()?;
+        Ok(matrix)
+    }
+
+    // the cache only saves time, so a broken one is rebuilt and a failed write ignored
+    pub fn cached(
+        dir: impl AsRef<Path>,
+        guesses: &[&'static str],
+        answers: &[&'static str],
+    ) -> Self {
+        let path = dir.as_ref().join(format!(
+            "patterns-{:016x}.bin",
+            Self::fingerprint(guesses, answers)
+        ));
+        if let Ok(matrix) = fs::File::open(&path)
+            .map_err(PatternCacheError::from)
+            .and_then(|file| Self::load(&mut BufReader::new(file), guesses, answers))
+        {
+            return matrix;
+        }
+        let matrix = Self::new(guesses, answers);
+        // written aside first, so a concurrent reader never sees half a file
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        let _ = fs::create_dir_all(dir.as_ref())
+            .and_then(|_| fs::File::create(&tmp_path))
+            .and_then(|file| matrix.save(&mut BufWriter::new(file)))
+            .and_then(|_| fs::rename(&tmp_path, &path))
+            .map_err(|_| fs::remove_file(&tmp_path));
+        matrix
+    }
+
+    pub fn from_dict_cached(dict: &Dict) -> Self {
+        match default_cache_dir() {
+            Some(dir) => Self::cached(dir, dict.words(), dict.words()),
+            None => Self::from_dict(dict),
+        }
+    }
+
+    // patterns of every guess for the answer, in guess order
+    #[inline]
+    pub fn answer_row(&self, answer: usize) -> &[Pattern] {
+        let guesses = self.guesses.len();
+        &self.patterns[answer * guesses..(answer + 1) * guesses]
+    }
+
+    pub fn bucket_sizes(&self, guess: usize, answers: &[usize]) -> Vec<usize> {
+        let mut sizes = vec![0; Pattern::count(self.word_len)];
+        for &answer in answers {
+            sizes[self.get(guess, answer).index()] += 1;
+        }
+        sizes
+    }
+
+    pub fn bucket_weights(&self, guess: usize, answers: &[usize], weights: &[f64]) -> Vec<f64> {
+        let mut bucket_weights = vec![0.0; Pattern::count(self.word_len)];
+        for (&answer, weight) in zip(answers, weights) {
+            bucket_weights[self.get(guess, answer).index()] += weight;
+        }
+        bucket_weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Attempt;
+    use std::io::Cursor;
+
+    fn saved(matrix: &PatternMatrix) -> Vec<u8> {
+        let mut out = vec![];
+        matrix.save(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn get() {
+        let dict = Dict::from_iter(["казан", "казна", "нотка", "сазан", "шалаш"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        assert_eq!(matrix.word_len(), 5);
+        for (guess_index, &guess) in matrix.guesses().iter().enumerate() {
+            for (answer_index, &answer) in matrix.answers().iter().enumerate() {
+                assert_eq!(
+                    matrix.get(guess_index, answer_index),
+                    Pattern::from(&Attempt::evaluate(guess, &answer.into()))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn indices() {
+        let matrix = PatternMatrix::new(&["казна", "нотка", "шалаш"], &["казан", "сазан"]);
+        assert_eq!(matrix.guess_index("нотка"), Some(1));
+        assert_eq!(matrix.guess_index("сазан"), None);
+        assert_eq!(matrix.answer_index("сазан"), Some(1));
+        assert_eq!(
+            matrix.get(0, 1).to_attempt("казна"),
+            "к а+з+н?а?".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn bucket_sizes() {
+        let dict = Dict::from_iter(["казан", "казна", "сазан", "фазан"]);
+        let matrix = PatternMatrix::from_dict(&dict);
+        let sizes = matrix.bucket_sizes(0, &[0, 1, 2, 3]);
+        assert_eq!(sizes.iter().sum::<usize>(), 4);
+        assert_eq!(sizes.iter().filter(|&&size| size > 0).count(), 3);
+        assert_eq!(sizes[Pattern::win(5).index()], 1);
+        let weights = matrix.bucket_weights(0, &[0, 2, 3], &[1.0, 2.0, 0.5]);
+        assert_eq!(weights.iter().sum::<f64>(), 3.5);
+        assert_eq!(weights[Pattern::win(5).index()], 1.0);
+    }
+
+    #[test]
+    fn fingerprint() {
+        let words = ["казан", "казна", "сазан"];
+        let fingerprint = PatternMatrix::fingerprint(&words, &words);
+        assert_eq!(fingerprint, PatternMatrix::fingerprint(&words, &words));
+        assert_ne!(fingerprint, PatternMatrix::fingerprint(&words, &words[1..]));
+        assert_ne!(fingerprint, PatternMatrix::fingerprint(&words[1..], &words));
+        assert_ne!(
+            fingerprint,
+            PatternMatrix::fingerprint(&words, &["казна", "казан", "сазан"])
+        );
+        assert_ne!(
+            PatternMatrix::fingerprint(&["каз", "ан"], &[]),
+            PatternMatrix::fingerprint(&["ка", "зан"], &[])
+        );
+    }
+
+    #[test]
+    fn check_dict() {
+        let dict = Dict::from_iter(["казан", "казна", "сазан"]);
+        assert!(PatternMatrix::from_dict(&dict).check_dict(&dict).is_ok());
+        assert_eq!(
+            PatternMatrix::new(&dict.words()[1..], dict.words()).check_dict(&dict),
+            Err(PatternCacheError::DictMismatch)
+        );
+        assert_eq!(
+            PatternMatrix::from_dict(&Dict::from_iter(["казан", "сазан"])).check_dict(&dict),
+            Err(PatternCacheError::DictMismatch)
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let dict = Dict::from_iter(["казан", "казна", "нотка", "сазан", "шалаш"]);
+        let matrix = PatternMatrix::new(&dict.words()[1..], dict.words());
+        let bytes = saved(&matrix);
+        assert_eq!(bytes.len(), 20 + 2 * 4 * 5);
+        let loaded =
+            PatternMatrix::load(&mut Cursor::new(&bytes), &dict.words()[1..], dict.words())
+                .unwrap();
+        assert_eq!(loaded, matrix);
+    }
+
+    #[test]
+    fn load_errors() {
+        let words = ["казан", "казна", "сазан"];
+        let bytes = saved(&PatternMatrix::new(&words, &words));
+        let load = |bytes: &[u8], answers: &[&'static str]| {
+            PatternMatrix::load(&mut Cursor::new(bytes), &words, answers).map(|_| ())
+        };
+        assert_eq!(load(&bytes, &words), Ok(()));
+        assert_eq!(load(&bytes, &words[1..]), Err(PatternCacheError::StaleDict));
+        assert_eq!(
+            load(&bytes[..bytes.len() - 1], &words),
+            Err(PatternCacheError::IoError(
+                io::ErrorKind::UnexpectedEof.into()
+            ))
+        );
+        assert_eq!(
+            load(&[&bytes[..], &[0]].concat(), &words),
+            Err(PatternCacheError::BadHeader)
+        );
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+        assert_eq!(load(&bad_magic, &words), Err(PatternCacheError::BadHeader));
+        let mut bad_pattern = bytes.clone();
+        bad_pattern[20..22].copy_from_slice(&243u16.to_le_bytes());
+        assert_eq!(
+            load(&bad_pattern, &words),
+            Err(PatternCacheError::InvalidPattern(243))
+        );
+    }
+
+    #[test]
+    fn cached() {
+        let dir = env::temp_dir().join(format!("mordle-test-{}", std::process::id()));
+        let words = ["казан", "казна", "сазан"];
+        let matrix = PatternMatrix::cached(&dir, &words, &words);
+        assert_eq!(matrix, PatternMatrix::new(&words, &words));
+        let files = fs::read_dir(&dir).unwrap().collect_vec();
+        assert_eq!(files.len(), 1);
+        let path = files[0].as_ref().unwrap().path();
+        // a corrupted cache is rebuilt
+        fs::write(&path, b"MORDLEPM").unwrap();
+        assert_eq!(PatternMatrix::cached(&dir, &words, &words), matrix);
+        assert_eq!(fs::read(&path).unwrap(), saved(&matrix));
+        // another dictionary gets its own file
+        PatternMatrix::cached(&dir, &words[1..], &words[1..]);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,770 @@
+use crate::{
+    solver::{self, Knowledge, Objective, PatternCacheError, PatternMatrix},
+    Attempt, CharPositions, Dict,
+};
+use std::{
+    borrow::Cow,
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+const DEFAULT_TIME_BUDGET: Duration = Duration::from_secs(1);
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Suggestion {
+    pub word: &'static str,
+    pub proven: bool,
+}
+
+pub trait Solver {
+    fn start(&mut self);
+    fn observe(&mut self, attempt: &Attempt);
+    fn suggest(&self) -> Option<&'static str>;
+    fn candidates(&self) -> &Dict;
+
+    // whether any candidate could have produced the feedback
+    fn is_consistent(&self, attempt: &Attempt) -> bool {
+        !Knowledge::from_attempts([attempt])
+            .filter_dict(self.candidates())
+            .words()
+            .is_empty()
+    }
+
+    // guesses by the information their feedback brings, most informative first
+    fn rank(&self, guesses: &Dict) -> Vec<(&'static str, f64)> {
+        solver::rank_by_entropy(guesses, self.candidates())
+    }
+
+    // heuristics know nothing better than their suggestion, however long they take
+    fn suggest_until(&self, _deadline: Instant) -> Option<Suggestion> {
+        self.suggestion()
+    }
+
+    fn suggestion(&self) -> Option<Suggestion> {
+        self.suggest().map(|word| Suggestion {
+            word,
+            proven: false,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Strategy {
+    Frequency,
+    Entropy,
+    Minimax,
+    ExpectedCost,
+    Anytime,
+}
+
+impl Strategy {
+    #[inline]
+    pub fn solver<'a>(self, dict: &'a Dict) -> Box<dyn Solver + 'a> {
+        self.solver_with_options(dict, SolverOptions::default())
+    }
+
+    pub fn solver_with_options<'a>(
+        self,
+        dict: &'a Dict,
+        options: SolverOptions,
+    ) -> Box<dyn Solver + 'a> {
+        match self {
+            Strategy::Frequency => Box::new(FrequencySolver::with_options(dict, options)),
+            Strategy::Entropy => Box::new(EntropySolver::with_options(dict, options)),
+            Strategy::Minimax => Box::new(MinimaxSolver::with_options(dict, options)),
+            Strategy::ExpectedCost => Box::new(ExpectedCostSolver::with_options(dict, options)),
+            Strategy::Anytime => Box::new(AnytimeSolver::with_options(dict, options)),
+        }
+    }
+
+    #[inline]
+    pub fn uses_matrix(self) -> bool {
+        self != Strategy::Frequency
+    }
+
+    // the matrix has to be built from the same dict, e.g. loaded from the cache
+    pub fn solver_with_matrix<'a>(
+        self,
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Result<Box<dyn Solver + 'a>, PatternCacheError> {
+        Ok(match self {
+            Strategy::Frequency => Box::new(FrequencySolver::with_options(dict, options)),
+            Strategy::Entropy => Box::new(EntropySolver::with_matrix(dict, options, matrix)?),
+            Strategy::Minimax => Box::new(MinimaxSolver::with_matrix(dict, options, matrix)?),
+            Strategy::ExpectedCost => {
+                Box::new(ExpectedCostSolver::with_matrix(dict, options, matrix)?)
+            }
+            Strategy::Anytime => Box::new(AnytimeSolver::with_matrix(dict, options, matrix)?),
+        })
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum ProbePolicy {
+    // frequency composes candidates, the scoring strategies probe freely
+    #[default]
+    Auto,
+    Never,
+    Always,
+    // while more candidates than this remain
+    MoreThan(usize),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct SolverOptions {
+    pub hard_mode: bool,
+    pub probe: ProbePolicy,
+    pub time_budget: Option<Duration>,
+}
+
+struct SolverState<'a> {
+    dict: &'a Dict,
+    options: SolverOptions,
+    knowledge: Knowledge,
+    candidates: Dict,
+    attempts: usize,
+    opening: Cell<Option<&'static str>>,
+}
+
+impl<'a> SolverState<'a> {
+    fn new(dict: &'a Dict, options: SolverOptions) -> Self {
+        Self {
+            dict,
+            options,
+            knowledge: Knowledge::default(),
+            candidates: dict.clone(),
+            attempts: 0,
+            opening: Cell::new(None),
+        }
+    }
+
+    fn start(&mut self) {
+        self.knowledge = Knowledge::default();
+        self.candidates = self.dict.clone();
+        self.attempts = 0;
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.knowledge.observe(attempt);
+        self.candidates = Knowledge::from_attempts([attempt]).filter_dict(&self.candidates);
+        self.attempts += 1;
+    }
+
+    // the opening is the most expensive suggestion and the same for every game
+    fn suggest(&self, suggest: impl FnOnce() -> Option<&'static str>) -> Option<&'static str> {
+        if self.attempts > 0 {
+            return suggest();
+        }
+        if self.opening.get().is_none() {
+            self.opening.set(suggest());
+        }
+        self.opening.get()
+    }
+
+    fn probes(&self, by_default: bool) -> bool {
+        match self.options.probe {
+            ProbePolicy::Auto => by_default,
+            ProbePolicy::Never => false,
+            ProbePolicy::Always => true,
+            ProbePolicy::MoreThan(count) => self.candidates.words().len() > count,
+        }
+    }
+
+    fn guesses(&self, probes_by_default: bool) -> Cow<'_, Dict> {
+        if !self.probes(probes_by_default) {
+            // candidates keep to hard mode as well
+            Cow::Borrowed(&self.candidates)
+        } else if self.options.hard_mode {
+            Cow::Owned(self.knowledge.hard_mode_filter(self.dict))
+        } else {
+            Cow::Borrowed(self.dict)
+        }
+    }
+}
+
+pub struct FrequencySolver<'a>(SolverState<'a>);
+
+impl<'a> FrequencySolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_options(dict, SolverOptions::default())
+    }
+
+    pub fn with_options(dict: &'a Dict, options: SolverOptions) -> Self {
+        Self(SolverState::new(dict, options))
+    }
+}
+
+impl Solver for FrequencySolver<'_> {
+    fn start(&mut self) {
+        self.0.start()
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.0.observe(attempt)
+    }
+
+    fn suggest(&self) -> Option<&'static str> {
+        let candidates = &self.0.candidates;
+        self.0.suggest(|| {
+            if self.0.probes(false) {
+                return solver::suggest_probe(&self.0.guesses(false), candidates);
+            }
+            // the letter heuristic may compose no word from candidates sharing letters
+            solver::suggest_word(candidates.clone())
+                .and_then(|chars| {
+                    let word = chars.into_iter().collect::<String>();
+                    candidates.words().iter().copied().find(|&w| w == word)
+                })
+                .or_else(|| candidates.words().first().copied())
+        })
+    }
+
+    #[inline]
+    fn candidates(&self) -> &Dict {
+        &self.0.candidates
+    }
+}
+
+pub struct EntropySolver<'a> {
+    state: SolverState<'a>,
+    matrix: PatternMatrix,
+}
+
+impl<'a> EntropySolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_options(dict, SolverOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(dict: &'a Dict, options: SolverOptions) -> Self {
+        Self::with_matrix_unchecked(dict, options, PatternMatrix::from_dict(dict))
+    }
+
+    pub fn with_matrix(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Result<Self, PatternCacheError> {
+        matrix.check_dict(dict)?;
+        Ok(Self::with_matrix_unchecked(dict, options, matrix))
+    }
+
+    fn with_matrix_unchecked(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Self {
+        Self {
+            state: SolverState::new(dict, options),
+            matrix,
+        }
+    }
+}
+
+impl Solver for EntropySolver<'_> {
+    fn start(&mut self) {
+        self.state.start()
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.state.observe(attempt)
+    }
+
+    fn suggest(&self) -> Option<&'static str> {
+        self.state.suggest(|| {
+            solver::suggest_word_by_entropy_with_matrix(
+                &self.matrix,
+                &self.state.guesses(true),
+                &self.state.candidates,
+            )
+        })
+    }
+
+    #[inline]
+    fn candidates(&self) -> &Dict {
+        &self.state.candidates
+    }
+}
+
+pub struct MinimaxSolver<'a> {
+    state: SolverState<'a>,
+    matrix: PatternMatrix,
+}
+
+impl<'a> MinimaxSolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_options(dict, SolverOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(dict: &'a Dict, options: SolverOptions) -> Self {
+        Self::with_matrix_unchecked(dict, options, PatternMatrix::from_dict(dict))
+    }
+
+    pub fn with_matrix(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Result<Self, PatternCacheError> {
+        matrix.check_dict(dict)?;
+        Ok(Self::with_matrix_unchecked(dict, options, matrix))
+    }
+
+    fn with_matrix_unchecked(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Self {
+        Self {
+            state: SolverState::new(dict, options),
+            matrix,
+        }
+    }
+}
+
+impl Solver for MinimaxSolver<'_> {
+    fn start(&mut self) {
+        self.state.start()
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.state.observe(attempt)
+    }
+
+    fn suggest(&self) -> Option<&'static str> {
+        self.state.suggest(|| {
+            solver::suggest_word_by_worst_case_with_matrix(
+                &self.matrix,
+                &self.state.guesses(true),
+                &self.state.candidates,
+            )
+        })
+    }
+
+    #[inline]
+    fn candidates(&self) -> &Dict {
+        &self.state.candidates
+    }
+}
+
+pub struct ExpectedCostSolver<'a> {
+    state: SolverState<'a>,
+    matrix: PatternMatrix,
+}
+
+impl<'a> ExpectedCostSolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_options(dict, SolverOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(dict: &'a Dict, options: SolverOptions) -> Self {
+        Self::with_matrix_unchecked(dict, options, PatternMatrix::from_dict(dict))
+    }
+
+    pub fn with_matrix(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Result<Self, PatternCacheError> {
+        matrix.check_dict(dict)?;
+        Ok(Self::with_matrix_unchecked(dict, options, matrix))
+    }
+
+    fn with_matrix_unchecked(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Self {
+        Self {
+            state: SolverState::new(dict, options),
+            matrix,
+        }
+    }
+}
+
+impl Solver for ExpectedCostSolver<'_> {
+    fn start(&mut self) {
+        self.state.start()
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.state.observe(attempt)
+    }
+
+    fn suggest(&self) -> Option<&'static str> {
+        self.state.suggest(|| {
+            solver::suggest_word_by_expected_cost_with_matrix(
+                &self.matrix,
+                &self.state.guesses(true),
+                &self.state.candidates,
+            )
+        })
+    }
+
+    #[inline]
+    fn candidates(&self) -> &Dict {
+        &self.state.candidates
+    }
+}
+
+pub struct AnytimeSolver<'a> {
+    state: SolverState<'a>,
+    matrix: PatternMatrix,
+    opening: Cell<Option<Suggestion>>,
+}
+
+impl<'a> AnytimeSolver<'a> {
+    #[inline]
+    pub fn new(dict: &'a Dict) -> Self {
+        Self::with_options(dict, SolverOptions::default())
+    }
+
+    #[inline]
+    pub fn with_options(dict: &'a Dict, options: SolverOptions) -> Self {
+        Self::with_matrix_unchecked(dict, options, PatternMatrix::from_dict(dict))
+    }
+
+    pub fn with_matrix(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Result<Self, PatternCacheError> {
+        matrix.check_dict(dict)?;
+        Ok(Self::with_matrix_unchecked(dict, options, matrix))
+    }
+
+    fn with_matrix_unchecked(
+        dict: &'a Dict,
+        options: SolverOptions,
+        matrix: PatternMatrix,
+    ) -> Self {
+        Self {
+            state: SolverState::new(dict, options),
+            matrix,
+            opening: Cell::new(None),
+        }
+    }
+}
+
+impl Solver for AnytimeSolver<'_> {
+    fn start(&mut self) {
+        self.state.start()
+    }
+
+    fn observe(&mut self, attempt: &Attempt) {
+        self.state.observe(attempt)
+    }
+
+    #[inline]
+    fn suggest(&self) -> Option<&'static str> {
+        self.suggestion().map(|suggestion| suggestion.word)
+    }
+
+    #[inline]
+    fn candidates(&self) -> &Dict {
+        &self.state.candidates
+    }
+
+    fn suggest_until(&self, deadline: Instant) -> Option<Suggestion> {
+        let candidates = &self.state.candidates;
+        if let [word] = candidates.words() {
+            return Some(Suggestion { word, proven: true });
+        }
+        let guesses = self.state.guesses(true);
+        // the heuristic answers at once if the search finds nothing in time
+        let heuristic =
+            solver::suggest_word_by_expected_cost_with_matrix(&self.matrix, &guesses, candidates)?;
+        Some(
+            match solver::anytime_tree(
+                &self.matrix,
+                &guesses,
+                candidates,
+                Objective::Expected,
+                deadline,
+            ) {
+                Some(tree) => Suggestion {
+                    word: tree.tree.guess,
+                    // the search doesn't keep to hard mode after the first guess
+                    proven: tree.proven && !self.state.options.hard_mode,
+                },
+                None => Suggestion {
+                    word: heuristic,
+                    proven: false,
+                },
+            },
+        )
+    }
+
+    fn suggestion(&self) -> Option<Suggestion> {
+        let budget = self
+            .state
+            .options
+            .time_budget
+            .unwrap_or(DEFAULT_TIME_BUDGET);
+        if self.state.attempts > 0 {
+            return self.suggest_until(Instant::now() + budget);
+        }
+        if self.opening.get().is_none() {
+            self.opening
+                .set(self.suggest_until(Instant::now() + budget));
+        }
+        self.opening.get()
+    }
+}
+
+pub fn auto_play(solver: &mut dyn Solver, secret: &str, max_tries: usize) -> Vec<Attempt> {
+    solver.start();
+    let char_positions = CharPositions::new(secret);
+    let mut attempts = vec![];
+    while attempts.len() < max_tries {
+        let Some(guess) = solver.suggest() else {
+            break;
+        };
+        let attempt = Attempt::evaluate(guess, &char_positions);
+        solver.observe(&attempt);
+        let is_win_attempt = attempt.is_win_attempt();
+        attempts.push(attempt);
+        if is_win_attempt {
+            break;
+        }
+    }
+    attempts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn small_dict() -> Dict {
+        Dict::from_iter([
+            "казан",
+            "казна",
+            "нотка",
+            "парад",
+            "парус",
+            "сазан",
+            "фазан",
+            "шакал",
+        ])
+    }
+
+    #[test]
+    fn frequency_suggest() {
+        let dict = Dict::default();
+        let mut solver = FrequencySolver::new(&dict);
+        assert_eq!(solver.suggest(), Some("щетка"));
+        solver.observe(&Attempt::evaluate("щетка", &"сазан".into()));
+        let suggestion = solver.suggest().unwrap();
+        assert!(solver.candidates().word_in_dict(suggestion));
+    }
+
+    #[test]
+    fn observe_and_start() {
+        let dict = small_dict();
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::ExpectedCost,
+        ] {
+            let mut solver = strategy.solver(&dict);
+            solver.observe(&Attempt::evaluate("казна", &"сазан".into()));
+            assert_eq!(solver.candidates().words(), ["сазан", "фазан"]);
+            solver.start();
+            assert_eq!(solver.candidates(), &dict);
+        }
+    }
+
+    #[test]
+    fn suggest_empty() {
+        let dict = Dict::empty();
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::ExpectedCost,
+        ] {
+            assert_eq!(strategy.solver(&dict).suggest(), None);
+        }
+    }
+
+    #[test]
+    fn auto_play() {
+        let dict = small_dict();
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::ExpectedCost,
+        ] {
+            let mut solver = strategy.solver(&dict);
+            for &secret in dict.words() {
+                let attempts = super::auto_play(solver.as_mut(), secret, 6);
+                assert!(attempts.last().unwrap().is_win_attempt());
+                assert_eq!(attempts.last().unwrap().word(), secret);
+            }
+        }
+    }
+
+    #[test]
+    fn hard_mode() {
+        let dict = Dict::default();
+        let mut candidates = dict.clone();
+        candidates.retain(|word| word.ends_with("айка") || word.starts_with("ш"));
+        let attempt = Attempt::evaluate("майка", &"шайка".into());
+        for strategy in [Strategy::Entropy, Strategy::Minimax, Strategy::ExpectedCost] {
+            let mut solver = strategy.solver(&candidates);
+            solver.observe(&attempt);
+            assert!(!solver.candidates().word_in_dict(solver.suggest().unwrap()));
+            let mut solver = strategy.solver_with_options(
+                &candidates,
+                SolverOptions {
+                    hard_mode: true,
+                    ..Default::default()
+                },
+            );
+            solver.observe(&attempt);
+            let suggestion = solver.suggest().unwrap();
+            assert!(Knowledge::from_attempts([&attempt]).hard_mode_allows(suggestion));
+        }
+    }
+
+    #[test]
+    fn hard_mode_auto_play() {
+        let dict = small_dict();
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::ExpectedCost,
+        ] {
+            let mut solver = strategy.solver_with_options(
+                &dict,
+                SolverOptions {
+                    hard_mode: true,
+                    ..Default::default()
+                },
+            );
+            for &secret in dict.words() {
+                let attempts = super::auto_play(solver.as_mut(), secret, 6);
+                assert!(attempts.last().unwrap().is_win_attempt());
+                for (i, attempt) in attempts.iter().enumerate().skip(1) {
+                    assert!(
+                        Knowledge::from_attempts(&attempts[..i]).hard_mode_allows(&attempt.word())
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn probe_policy() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        let attempt = Attempt::evaluate("майка", &"шайка".into());
+        let suggest = |strategy: Strategy, probe| {
+            let mut solver = strategy.solver_with_options(
+                &dict,
+                SolverOptions {
+                    probe,
+                    ..Default::default()
+                },
+            );
+            solver.observe(&attempt);
+            let suggestion = solver.suggest().unwrap();
+            solver.candidates().word_in_dict(suggestion)
+        };
+        let candidates = Knowledge::from_attempts([&attempt]).filter_dict(&dict);
+        assert!(candidates.words().len() > 3);
+        for strategy in [
+            Strategy::Frequency,
+            Strategy::Entropy,
+            Strategy::Minimax,
+            Strategy::ExpectedCost,
+        ] {
+            assert!(suggest(strategy, ProbePolicy::Never));
+            assert!(!suggest(strategy, ProbePolicy::Always));
+            assert!(!suggest(strategy, ProbePolicy::MoreThan(3)));
+            assert!(suggest(
+                strategy,
+                ProbePolicy::MoreThan(candidates.words().len())
+            ));
+        }
+        assert!(suggest(Strategy::Frequency, ProbePolicy::Auto));
+        assert!(!suggest(Strategy::Entropy, ProbePolicy::Auto));
+    }
+
+    #[test]
+    fn probe_policy_hard_mode() {
+        let dict = Dict::default();
+        let attempt = Attempt::evaluate("майка", &"шайка".into());
+        let mut solver = FrequencySolver::with_options(
+            &dict,
+            SolverOptions {
+                hard_mode: true,
+                probe: ProbePolicy::Always,
+                ..Default::default()
+            },
+        );
+        solver.observe(&attempt);
+        let suggestion = solver.suggest().unwrap();
+        assert!(Knowledge::from_attempts([&attempt]).hard_mode_allows(suggestion));
+    }
+
+    #[test]
+    fn anytime() {
+        let mut dict = Dict::default();
+        dict.retain(|word| word.ends_with("айка") || word.starts_with('ш'));
+        let mut solver = AnytimeSolver::with_options(
+            &dict,
+            SolverOptions {
+                time_budget: Some(Duration::from_secs(3600)),
+                ..Default::default()
+            },
+        );
+        solver.observe(&Attempt::evaluate("шалаш", &"майка".into()));
+        let suggestion = solver.suggestion().unwrap();
+        assert!(suggestion.proven);
+        let candidates = Dict::from_iter(solver.candidates().words().iter().copied());
+        let optimal = solver::optimal_tree(&dict, &candidates, Objective::Expected, None).unwrap();
+        assert_eq!(suggestion.word, optimal.tree.guess);
+        assert_eq!(solver.suggest(), Some(suggestion.word));
+
+        let expired = solver.suggest_until(Instant::now()).unwrap();
+        assert!(!expired.proven);
+        assert_eq!(
+            Some(expired.word),
+            solver::suggest_word_by_expected_cost(&dict, solver.candidates())
+        );
+        assert_eq!(
+            FrequencySolver::new(&dict).suggest_until(Instant::now() + Duration::from_secs(1)),
+            FrequencySolver::new(&dict).suggestion()
+        );
+    }
+
+    #[test]
+    fn anytime_auto_play() {
+        let dict = small_dict();
+        let mut solver = AnytimeSolver::new(&dict);
+        for &secret in dict.words() {
+            let attempts = super::auto_play(&mut solver, secret, 6);
+            assert!(attempts.last().unwrap().is_win_attempt());
+        }
+    }
+
+    #[test]
+    fn auto_play_out_of_tries() {
+        let dict = small_dict();
+        let mut solver = FrequencySolver::new(&dict);
+        let attempts = super::auto_play(&mut solver, "фазан", 1);
+        assert_eq!(attempts.len(), 1);
+        assert!(!attempts[0].is_win_attempt());
+    }
+}
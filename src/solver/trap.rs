@@ -0,0 +1,77 @@
+use crate::Dict;
+use itertools::Itertools;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TrapFamily {
+    pub pattern: String,
+    pub words: Vec<&'static str>,
+}
+
+// in hard mode every guess has to keep the shared letters, so a family
+// may cost as many guesses as it has words
+pub fn trap_families(dict: &Dict, min_size: usize) -> Vec<TrapFamily> {
+    dict.words()
+        .iter()
+        .flat_map(|&word| {
+            let chars = word.chars().collect_vec();
+            (0..chars.len()).map(move |pos| {
+                let pattern = chars
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &ch)| if i == pos { '_' } else { ch })
+                    .collect::<String>();
+                (pattern, word)
+            })
+        })
+        .into_group_map()
+        .into_iter()
+        .filter(|(_, words)| words.len() >= min_size)
+        .map(|(pattern, words)| TrapFamily { pattern, words })
+        .sorted_by(|a, b| {
+            a.words
+                .len()
+                .cmp(&b.words.len())
+                .reverse()
+                .then_with(|| a.pattern.cmp(&b.pattern))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trap_families() {
+        let dict = Dict::default();
+        let families = super::trap_families(&dict, 3);
+        assert_eq!(
+            families.first(),
+            Some(&TrapFamily {
+                pattern: "_айка".to_string(),
+                words: vec![
+                    "байка",
+                    "гайка",
+                    "зайка",
+                    "лайка",
+                    "майка",
+                    "пайка",
+                    "сайка",
+                    "чайка",
+                    "шайка"
+                ],
+            })
+        );
+        assert!(families.contains(&TrapFamily {
+            pattern: "_азан".to_string(),
+            words: vec!["казан", "сазан", "фазан"],
+        }));
+        assert!(families.iter().all(|family| family.words.len() >= 3));
+    }
+
+    #[test]
+    fn trap_families_none() {
+        let dict = Dict::from_iter(["казан", "нотка", "парус"]);
+        assert_eq!(super::trap_families(&dict, 2), vec![]);
+    }
+}